@@ -0,0 +1,26 @@
+// Generates the C header for the functions in src/ffi.rs and the plugin types in
+// src/plugin.rs. It's written to OUT_DIR, and only copied over the checked-in
+// include/aoc_template.h when AOC_UPDATE_HEADER is set. tests/c_abi.rs fails when
+// the two differ.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+  println!("cargo:rerun-if-changed=src/ffi.rs");
+  println!("cargo:rerun-if-changed=src/plugin.rs");
+  println!("cargo:rerun-if-changed=cbindgen.toml");
+  println!("cargo:rerun-if-env-changed=AOC_UPDATE_HEADER");
+  let config = cbindgen::Config::from_file("cbindgen.toml").expect("Could not read cbindgen.toml");
+  let header = PathBuf::from(env::var("OUT_DIR").unwrap()).join("aoc_template.h");
+  cbindgen::Builder::new()
+    .with_config(config)
+    .with_src("src/ffi.rs")
+    .with_src("src/plugin.rs")
+    .generate()
+    .expect("Could not generate the C header")
+    .write_to_file(&header);
+  if env::var_os("AOC_UPDATE_HEADER").is_some() {
+    fs::copy(&header, "include/aoc_template.h").expect("Could not update include/aoc_template.h");
+  }
+}
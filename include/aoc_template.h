@@ -0,0 +1,113 @@
+#ifndef AOC_TEMPLATE_H
+#define AOC_TEMPLATE_H
+
+/* Generated from src/ffi.rs and src/plugin.rs by build.rs. Don't edit this file by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The version of `AocPluginDescriptor` and the functions in it. Changes
+ * whenever any of them change.
+ */
+#define AOC_PLUGIN_ABI_VERSION 1
+
+/**
+ * What `aoc_solve` returns.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  /**
+   * Only the year of this crate is solved.
+   */
+  AOC_STATUS_UNKNOWN_YEAR = 1,
+  /**
+   * Days go from 1 to 25.
+   */
+  AOC_STATUS_UNKNOWN_DAY = 2,
+  /**
+   * Parts are 1 or 2.
+   */
+  AOC_STATUS_UNKNOWN_PART = 3,
+  /**
+   * The input or answer pointer is null, or the input is not UTF-8.
+   */
+  AOC_STATUS_INVALID_INPUT = 4,
+  /**
+   * The solution could not parse the input.
+   */
+  AOC_STATUS_PARSE_ERROR = 5,
+  /**
+   * The solution panicked, like days that aren't implemented yet.
+   */
+  AOC_STATUS_PANICKED = 6,
+  /**
+   * The answer has a NUL byte in it, so it can't be a C string.
+   */
+  AOC_STATUS_INVALID_ANSWER = 7,
+} AocStatus;
+
+/**
+ * Solves a part for the input of `input_len` bytes at `input`, with the same
+ * contract as `aoc_solve`: it returns one of the `AocStatus` values, and on
+ * success points `*answer` to a NUL-terminated string that is freed with the
+ * plugin's `free_answer`.
+ */
+typedef int32_t (*AocPartFn)(const uint8_t *input, size_t input_len, char **answer);
+
+/**
+ * Frees an answer returned by one of the part functions of the same plugin.
+ */
+typedef void (*AocFreeFn)(char *answer);
+
+/**
+ * What a plugin solves, and how.
+ */
+typedef struct AocPluginDescriptor {
+  uint32_t year;
+  uint32_t day;
+  AocPartFn part_1;
+  AocPartFn part_2;
+  AocFreeFn free_answer;
+} AocPluginDescriptor;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves one part of a day for the input of `input_len` bytes at `input_ptr`.
+ * On success, `*answer` points to the answer as a NUL-terminated string, which
+ * the caller owns and has to give back to `aoc_free_answer`. Otherwise
+ * `*answer` is set to null.
+ *
+ * # Safety
+ *
+ * `input_ptr` must point to `input_len` readable bytes, and `answer` must
+ * point to writable memory for a pointer.
+ */
+enum AocStatus aoc_solve(uint32_t year,
+                         uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input_ptr,
+                         size_t input_len,
+                         char **answer);
+
+/**
+ * Frees an answer returned by `aoc_solve`. Does nothing for null.
+ *
+ * # Safety
+ *
+ * `answer` must be null or a pointer from `aoc_solve` that hasn't been freed
+ * yet.
+ */
+void aoc_free_answer(char *answer);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC_TEMPLATE_H */
@@ -1,6 +1,10 @@
+use nom::character::complete::not_line_ending;
+use nom::combinator::map as pmap;
 use nom::IResult;
+use crate::utils::lines;
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day01;
 
@@ -29,14 +33,18 @@ impl Day for Day01 {
   type Input = Vec<String>;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
-    // Faking a nom parser, since we really just need to split by lines.
-    let ls = input.lines().map(String::from);
-    Ok(("", ls.collect()))
+    lines(pmap(not_line_ending, String::from))(input)
+  }
+
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
   }
 
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input
       .iter()
       .map(|l| check_line(l))
@@ -45,7 +53,7 @@ impl Day for Day01 {
 
   type Output2 = u32;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     input
       .iter()
       .map(|l| check_line(&replace_words(l)))
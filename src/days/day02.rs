@@ -3,10 +3,12 @@ use nom::bytes::complete::tag;
 use nom::character::complete::{newline, u32};
 use nom::combinator::map as pmap;
 use nom::multi::separated_list0;
-use nom::sequence::pair;
+use nom::sequence::{pair, preceded};
 use nom::IResult;
+use crate::utils::field;
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day02;
 
@@ -56,10 +58,10 @@ fn parse_cube_set(input: &str) -> IResult<&str, CubeSet> {
 }
 
 fn parse_game(input: &str) -> IResult<&str, Game> {
-  let (input, _) = tag("Game ")(input)?;
-  let (input, id) = u32(input)?;
-  let (input, _) = tag(": ")(input)?;
-  let (input, cubes) = separated_list0(tag("; "), parse_cube_set)(input)?;
+  let (input, (id, cubes)) = field(
+    preceded(tag("Game "), u32),
+    separated_list0(tag("; "), parse_cube_set),
+  )(input)?;
   let res = Game { id, cubes };
   Ok((input, res))
 }
@@ -75,9 +77,15 @@ impl Day for Day02 {
     separated_list0(newline, parse_game)(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input.iter().fold(0, |acc, Game { id, cubes }| {
       if cubes
         .iter()
@@ -92,7 +100,7 @@ impl Day for Day02 {
 
   type Output2 = u32;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     input
       .iter()
       .map(|Game { id: _, cubes }| {
@@ -5,6 +5,7 @@ use nom::{
 };
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day03;
 
@@ -68,9 +69,15 @@ impl Day for Day03 {
     separated_list0(newline, many0(none_of("\n")))(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     let (numbers, symbols) = get_nrs_and_symbols(input);
 
     let part_numbers = numbers.iter().filter(|((x, y), width, _)| {
@@ -84,7 +91,7 @@ impl Day for Day03 {
 
   type Output2 = u32;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let (numbers, symbols) = get_nrs_and_symbols(input);
     symbols
       .iter()
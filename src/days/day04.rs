@@ -1,11 +1,14 @@
 use nom::bytes::complete::tag;
 use nom::{
-  character::complete::{newline, space1, u32},
+  character::complete::{newline, space0, space1, u32},
   multi::separated_list0,
+  sequence::{pair, tuple},
   IResult,
 };
+use crate::utils::{field, spaced_numbers};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day04;
 
@@ -16,16 +19,9 @@ pub struct Card {
 }
 
 fn parse_card(input: &str) -> IResult<&str, Card> {
-  let (input, _) = tag("Card")(input)?;
-  let (input, _) = space1(input)?;
-  let (input, _) = u32(input)?;
-  let (input, _) = tag(":")(input)?;
-  let (input, _) = space1(input)?;
-  let (input, winning) = separated_list0(space1, u32)(input)?;
-  let (input, _) = space1(input)?;
-  let (input, _) = tag("|")(input)?;
-  let (input, _) = space1(input)?;
-  let (input, having) = separated_list0(space1, u32)(input)?;
+  let (input, (_, winning)) = field(pair(tag("Card"), pair(space1, u32)), spaced_numbers)(input)?;
+  let (input, _) = tuple((space0, tag("|"), space0))(input)?;
+  let (input, having) = spaced_numbers(input)?;
   Ok((input, Card { winning, having }))
 }
 
@@ -36,9 +32,15 @@ impl Day for Day04 {
     separated_list0(newline, parse_card)(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input
       .iter()
       .map(|c| 1 << (c.having.iter().filter(|n| c.winning.contains(n)).count()) >> 1)
@@ -47,7 +49,7 @@ impl Day for Day04 {
 
   type Output2 = u32;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let mut matching: Vec<(u32, usize)> = input
       .iter()
       .map(|c| (1, c.having.iter().filter(|n| c.winning.contains(n)).count()))
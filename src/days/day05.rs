@@ -1,11 +1,12 @@
-use itertools::Itertools;
+use crate::utils::{blank_line, blocks, Interval, RangeSet};
 use nom::bytes::complete::tag;
 use nom::character::complete::{alpha1, newline, space1, u64};
 use nom::multi::separated_list0;
-use nom::sequence::{pair, separated_pair};
+use nom::sequence::separated_pair;
 use nom::IResult;
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day05;
 
@@ -37,8 +38,8 @@ pub struct Almanac {
 fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
   let (input, _) = tag("seeds: ")(input)?;
   let (input, seeds) = separated_list0(space1, u64)(input)?;
-  let (input, _) = pair(newline, newline)(input)?;
-  let (input, maps) = separated_list0(pair(newline, newline), parse_map)(input)?;
+  let (input, _) = blank_line(input)?;
+  let (input, maps) = blocks(parse_map)(input)?;
   let res = Almanac { seeds, maps };
   Ok((input, res))
 }
@@ -50,9 +51,15 @@ impl Day for Day05 {
     parse_almanac(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u64;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     *input
       .seeds
       .iter()
@@ -78,68 +85,19 @@ impl Day for Day05 {
 
   type Output2 = u64;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    // Get the initial ranges
-    let mut seed_ranges: Vec<(u64, u64)> = input
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    let mut seeds: RangeSet<u64> = input
       .seeds
       .chunks(2)
-      .map(|pair| (pair[0], pair[0] + pair[1] - 1))
+      .map(|pair| Interval::with_len(pair[0], pair[1]))
       .collect();
-    // For each set of maps
     for m in input.maps.iter() {
-      // Apply the maps to the ranges, potentially splitting them up into multiple sub-ranges
-      seed_ranges = seed_ranges
-        .iter()
-        .flat_map(|(start, end)| {
-          // Get all ranges that overlap the input range
-          let overlaps: Vec<&(u64, u64, u64)> = m
-            .ranges
-            .iter()
-            .filter(|(_, mstart, mlength)| {
-              let mend = mstart + mlength - 1;
-              mstart <= end && mend >= *start
-            })
-            .sorted_by(|a, b| Ord::cmp(&a.1, &b.1))
-            .collect();
-          
-          // Split the input range into subranges at the start of every map range
-          // that starts in between the input range
-          let mut new_ranges: Vec<(u64, u64)> = vec![];
-          let (mut current_start, current_end) = (*start, *end);
-          for (_, o_start, o_length) in overlaps.iter() {
-            if *o_start > current_start {
-              new_ranges.push((current_start, o_start - 1));
-              current_start = *o_start;
-              let o_end = o_start + o_length - 1;
-              if o_end < current_end {
-                new_ranges.push((current_start, o_end));
-                current_start = o_end + 1;
-              }
-            }
-          }
-          new_ranges.push((current_start, current_end));
-
-          // For all these subranges, apply the remapping.
-          new_ranges
-            .iter()
-            .map(|(start, end)| {
-              match overlaps.iter().find(|(_, o_start, o_length)| {
-                o_start <= start && (o_start + o_length - 1) >= *end
-              }) {
-                Some((o_dest, o_start, _)) => {
-                  (start - o_start + o_dest, end - o_start + o_dest)
-                }
-                None => (*start, *end),
-              }
-            })
-            .collect::<Vec<(u64, u64)>>()
-        })
-        .collect();
+      seeds = seeds.map_pieces(
+        m.ranges
+          .iter()
+          .map(|(dest, src, length)| (Interval::with_len(*src, *length), *dest)),
+      );
     }
-    *seed_ranges
-      .iter()
-      .map(|(start, _)| *start)
-      .min()
-      .get_or_insert(0)
+    seeds.min().unwrap_or(0)
   }
 }
@@ -1,9 +1,9 @@
-use nom::bytes::complete::tag;
-use nom::character::complete::{newline, space1, u64};
-use nom::multi::separated_list0;
+use nom::character::complete::newline;
 use nom::IResult;
+use crate::utils::{labelled, spaced_numbers};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day06;
 
@@ -43,25 +43,27 @@ impl Day for Day06 {
   type Input = Vec<(u64, u64)>;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
-    let (input, _) = tag("Time:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, times) = separated_list0(space1, u64)(input)?;
+    let (input, times) = labelled("Time", spaced_numbers::<u64>)(input)?;
     let (input, _) = newline(input)?;
-    let (input, _) = tag("Distance:")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, distances) = separated_list0(space1, u64)(input)?;
+    let (input, distances) = labelled("Distance", spaced_numbers::<u64>)(input)?;
     Ok((input, times.into_iter().zip(distances).collect()))
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input.iter().map(run_race).product::<usize>()
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let time = combine_numbers(&input.iter().map(|x| x.0).collect::<Vec<u64>>());
     let distance = combine_numbers(&input.iter().map(|x| x.1).collect::<Vec<u64>>());
     run_race(&(time, distance))
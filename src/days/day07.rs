@@ -1,4 +1,5 @@
 use crate::days::Day;
+use crate::MyErr;
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::character::complete::{char, newline, space1, u64};
@@ -155,9 +156,15 @@ impl Day for Day07 {
     separated_list0(newline, parse_line)(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u64;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input
       .iter()
       .sorted_by(|h1, h2| cmp_hands(h1, h2))
@@ -168,7 +175,7 @@ impl Day for Day07 {
 
   type Output2 = u64;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     input
       .iter()
       .sorted_by(|h1, h2| cmp_hands_2(h1, h2))
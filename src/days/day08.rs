@@ -1,27 +1,20 @@
-use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{alphanumeric1, char, newline};
-use nom::combinator::map as pmap;
+use nom::character::complete::{alphanumeric1, newline, one_of};
+use nom::combinator::map;
 use nom::multi::{many1, separated_list0};
 use nom::IResult;
-use num::integer::lcm;
-use std::collections::HashMap;
+use crate::utils::{first_common_time, Direction, HashMap};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day08;
 
-#[derive(Debug, Clone, Copy)]
-pub enum Dir {
-  L,
-  R,
-}
-
 type Node = (String, (String, String));
 
 #[derive(Debug)]
 pub struct Instructions {
-  dirs: Vec<Dir>,
+  dirs: Vec<Direction>,
   nodes: HashMap<String, (String, String)>,
 }
 
@@ -35,22 +28,48 @@ fn parse_node(input: &str) -> IResult<&str, Node> {
   Ok((input, (own.to_owned(), (left.to_owned(), right.to_owned()))))
 }
 
-fn lcms(nrs: &[usize]) -> usize {
-  let mut res: usize = **nrs.first().get_or_insert(&0);
-  for n in nrs.iter().skip(1) {
-    res = lcm(*n, res);
+// The instructions only ever say L or R, so they're read as W and E
+fn parse_branch(input: &str) -> IResult<&str, Direction> {
+  map(one_of("LR"), |c| if c == 'L' { Direction::W } else { Direction::E })(input)
+}
+
+// Only W and E come out of `parse_branch`
+fn take_branch((left, right): (String, String), dir: Direction) -> String {
+  if dir == Direction::W {
+    left
+  } else {
+    right
+  }
+}
+
+fn is_end(pos: &str) -> bool {
+  pos.chars().nth(2) == Some('Z')
+}
+
+// The step at which a ghost first gets to an end, and how many steps it takes
+// from there to get to an end again. Assumes that it keeps going around the same
+// loop, like it does in every input.
+fn ghost_cycle(input: &Instructions, start: &str) -> (i128, i128) {
+  let mut pos = start.to_owned();
+  let mut first_end = None;
+  for i in 0.. {
+    if is_end(&pos) {
+      match first_end {
+        None => first_end = Some(i),
+        Some(first) => return (first as i128, (i - first) as i128),
+      }
+    }
+    let current_node = input.nodes[&pos].clone();
+    pos = take_branch(current_node, input.dirs[i % input.dirs.len()]);
   }
-  res
+  unreachable!()
 }
 
 impl Day for Day08 {
   type Input = Instructions;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
-    let (input, dirs) = many1(alt((
-      pmap(char('L'), |_| Dir::L),
-      pmap(char('R'), |_| Dir::R),
-    )))(input)?;
+    let (input, dirs) = many1(parse_branch)(input)?;
     let (input, _) = many1(newline)(input)?;
     let (input, nodes) = separated_list0(newline, parse_node)(input)?;
     let m = HashMap::from_iter(nodes);
@@ -58,53 +77,34 @@ impl Day for Day08 {
     Ok((input, res))
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     let mut pos = "AAA".to_string();
     for i in 0.. {
       if pos == "ZZZ" {
         return i as u32;
       }
       let current_node = input.nodes[&pos].clone();
-      match input.dirs[i % input.dirs.len()] {
-        Dir::L => pos = current_node.0,
-        Dir::R => pos = current_node.1,
-      }
+      pos = take_branch(current_node, input.dirs[i % input.dirs.len()]);
     }
     0
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    let mut poses: Vec<String> = input
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    let cycles = input
       .nodes
       .keys()
       .filter(|s| s.chars().nth(2) == Some('A'))
-      .cloned()
-      .collect();
-    let mut steps_needed: Vec<usize> = vec![];
-    for i in 0.. {
-      if poses.is_empty() {
-        break;
-      }
-      poses = poses
-        .iter()
-        .filter_map(|pos| {
-          if pos.chars().nth(2) == Some('Z') {
-            steps_needed.push(i);
-            None
-          } else {
-            let current_node = input.nodes[pos].clone();
-            match input.dirs[i % input.dirs.len()] {
-              Dir::L => Some(current_node.0),
-              Dir::R => Some(current_node.1),
-            }
-          }
-        })
-        .collect();
-    }
-    lcms(&steps_needed)
+      .map(|start| ghost_cycle(input, start));
+    first_common_time(cycles).expect("The ghosts are never all at an end at once") as usize
   }
 }
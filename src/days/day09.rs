@@ -1,46 +1,44 @@
-use nom::character::complete::{i32, newline, space1};
-use nom::multi::separated_list0;
 use nom::IResult;
+use num::BigInt;
+use crate::utils::{lines, spaced_numbers, Sequence};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day09;
 
-fn estimate_next(row: &[i32]) -> i32 {
-  let differences: Vec<i32> = (1..row.len()).map(|i| row[i] - row[i - 1]).collect();
-  let first = differences.first();
-  // Check if they're all equal
-  row.last().unwrap()
-    + if differences
-      .iter()
-      .fold(first, |acc, x| if Some(x) == acc { acc } else { None })
-      .is_some()
-    {
-      *first.unwrap()
-    } else {
-      estimate_next(&differences)
-    }
-}
 
 impl Day for Day09 {
   type Input = Vec<Vec<i32>>;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
-    separated_list0(newline, separated_list0(space1, i32))(input)
+    lines(spaced_numbers)(input)
   }
 
-  type Output1 = i32;
+  // The sequence of every row, with the index right after its last sample. The
+  // values far outside the samples can get large, so they're BigInts.
+  type Prepared = Vec<(Sequence, i64)>;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    input.iter().map(|x| estimate_next(x)).sum::<i32>()
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    input
+      .iter()
+      .map(|row| {
+        Sequence::from_samples(row)
+          .map(|seq| (seq, row.len() as i64))
+          .map_err(|e| MyErr::Other(e.to_string()))
+      })
+      .collect()
   }
 
-  type Output2 = i32;
+  type Output1 = BigInt;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    input
-      .iter()
-      .map(|x| estimate_next(&x.iter().rev().copied().collect::<Vec<i32>>()))
-      .sum::<i32>()
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    input.iter().map(|(seq, next)| seq.at(*next)).sum::<BigInt>()
+  }
+
+  type Output2 = BigInt;
+
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    input.iter().map(|(seq, _)| seq.at(-1)).sum::<BigInt>()
   }
 }
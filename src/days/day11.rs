@@ -3,9 +3,10 @@ use nom::character::complete::{char, newline};
 use nom::combinator::map as pmap;
 use nom::multi::{many1, separated_list0};
 use nom::IResult;
-use std::collections::HashSet;
+use crate::utils::{HashSet, Point2};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day11;
 
@@ -68,12 +69,11 @@ fn solve(steps: usize, input: &[Vec<Pixel>]) -> usize {
   galaxies
     .iter()
     .enumerate()
-    .map(|(i, (gx, gy))| {
+    .map(|(i, g)| {
       galaxies
-        .clone()
         .iter()
         .skip(i + 1)
-        .map(|(gx2, gy2)| gx.abs_diff(*gx2) + gy.abs_diff(*gy2))
+        .map(|g2| Point2::from(*g).manhattan(Point2::from(*g2)))
         .sum::<usize>()
     })
     .sum()
@@ -86,15 +86,21 @@ impl Day for Day11 {
     separated_list0(newline, many1(parse_pixel))(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     solve(2, input)
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     solve(1000000, input)
   }
 }
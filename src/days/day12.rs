@@ -1,9 +1,10 @@
 use nom::character::complete::{char, newline, one_of, space1, u16};
 use nom::multi::{many1, separated_list0};
 use nom::IResult;
-use std::collections::HashMap;
+use crate::utils::Memo;
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day12;
 
@@ -16,16 +17,18 @@ fn parse_row(input: &str) -> IResult<&str, Row> {
   Ok((input, (conds.into_iter().collect(), nums)))
 }
 
-fn solve(
-  s: &str,
-  working_on: Option<u16>,
-  nrs: &[u16],
-  memo: &mut HashMap<(String, Option<u16>, Vec<u16>), usize>,
-) -> usize {
-  if let Some(res) = memo.get(&(s.to_owned(), working_on, nrs.to_vec())) {
-    return *res;
-  }
-  let res = if s.is_empty() {
+// The rest of the row and of the numbers are always suffixes of the whole row and
+// numbers, so their lengths are enough to tell them apart in the memo
+type SolveMemo = Memo<(usize, Option<u16>, usize), usize>;
+
+fn solve(s: &str, working_on: Option<u16>, nrs: &[u16], memo: &mut SolveMemo) -> usize {
+  memo.get_or_compute((s.len(), working_on, nrs.len()), |memo| {
+    solve_uncached(s, working_on, nrs, memo)
+  })
+}
+
+fn solve_uncached(s: &str, working_on: Option<u16>, nrs: &[u16], memo: &mut SolveMemo) -> usize {
+  if s.is_empty() {
     if (working_on == Some(0) || working_on.is_none()) && nrs.is_empty() {
       1
     } else {
@@ -53,9 +56,7 @@ fn solve(
       ('?', Some(x)) => solve(&s[1..], Some(x - 1), nrs, memo),
       _ => panic!("Unexpected character"),
     }
-  };
-  memo.insert((s.to_owned(), working_on, nrs.to_vec()), res);
-  res
+  }
 }
 
 impl Day for Day12 {
@@ -65,18 +66,29 @@ impl Day for Day12 {
     separated_list0(newline, parse_row)(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    let mut memo = Memo::new();
     input
       .iter()
-      .map(|(r, nrs)| solve(r, None, nrs, &mut HashMap::new()))
+      .map(|(r, nrs)| {
+        memo.clear();
+        solve(r, None, nrs, &mut memo)
+      })
       .sum()
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    let mut memo = Memo::new();
     input
       .iter()
       .map(|(r, ns)| {
@@ -87,7 +99,8 @@ impl Day for Day12 {
           .copied()
           .collect();
         // let s: Vec<String> = new_r.split('.').filter_map(|s| if s != "" {Some(s.to_owned())} else {None}).collect();
-        solve(&new_r, None, &new_ns, &mut HashMap::new())
+        memo.clear();
+        solve(&new_r, None, &new_ns, &mut memo)
       })
       .sum()
   }
@@ -2,10 +2,11 @@ use nom::branch::alt;
 use nom::character::complete::{char, newline};
 use nom::combinator::map as pmap;
 use nom::multi::{many1, separated_list0};
-use nom::sequence::pair;
 use nom::IResult;
+use crate::utils::blocks;
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day13;
 
@@ -66,12 +67,18 @@ impl Day for Day13 {
   type Input = Vec<Map>;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
-    separated_list0(pair(newline, newline), parse_map)(input)
+    blocks(parse_map)(input)
+  }
+
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
   }
 
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input
       .iter()
       .map(|m| {
@@ -83,7 +90,7 @@ impl Day for Day13 {
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     input
       .iter()
       .map(|m| {
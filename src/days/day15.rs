@@ -7,9 +7,10 @@ use nom::{
   sequence::pair,
   IResult,
 };
-use std::collections::HashMap;
+use crate::utils::HashMap;
 
 use crate::days::Day;
+use crate::MyErr;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Node {
@@ -43,15 +44,21 @@ impl Day for Day15 {
     Ok((input, res))
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     input.iter().map(|x| get_hash(x)).sum::<u32>()
   }
 
   type Output2 = u32;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let nodes: Vec<Node> = input
       .iter()
       .map(|s| {
@@ -59,7 +66,7 @@ impl Day for Day15 {
         parse_node(&s1).unwrap().1
       })
       .collect();
-    let mut hm: HashMap<u32, Vec<(Vec<char>, u32)>> = HashMap::new();
+    let mut hm: HashMap<u32, Vec<(Vec<char>, u32)>> = HashMap::default();
     for i in 0..256 {
       hm.insert(i, vec![]);
     }
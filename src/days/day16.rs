@@ -3,9 +3,9 @@ use nom::branch::alt;
 use nom::character::complete::char;
 use nom::combinator::map as pmap;
 use nom::IResult;
-use std::collections::HashSet;
 
 use crate::days::Day;
+use crate::MyErr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Tile {
@@ -26,63 +26,86 @@ fn parse_tile(input: &str) -> IResult<&str, Tile> {
   ))(input)
 }
 
-fn run(map: &Grid<Tile>, current_pos: Pos, current_dir: Pos, seen: &mut HashSet<(Pos, Pos)>) {
-  if let Some(t) = map.get(&current_pos) {
-    let key = (current_pos, current_dir);
-    if !seen.insert(key) {
+fn run(
+  map: &Grid<Tile>,
+  current_pos: Pos,
+  current_dir: Direction,
+  seen: &mut BitGrid,
+) {
+  if let Some(t) = map.get(current_pos) {
+    if !seen.insert_dir(current_pos, current_dir) {
       return;
     }
     match t {
-      Tile::Empty => run(map, current_pos + current_dir, current_dir, seen),
+      Tile::Empty => run(map, current_pos + current_dir.to_pos(), current_dir, seen),
       Tile::NEMirror => {
-        let new_dir = if current_dir.y == 0 {
-          rotate_ccw(current_dir)
+        let new_dir = if current_dir.is_horizontal() {
+          current_dir.turn_left()
         } else {
-          rotate_cw(current_dir)
+          current_dir.turn_right()
         };
-        run(map, current_pos + new_dir, new_dir, seen);
+        run(map, current_pos + new_dir.to_pos(), new_dir, seen);
       }
       Tile::SEMirror => {
-        let new_dir = if current_dir.y == 0 {
-          rotate_cw(current_dir)
+        let new_dir = if current_dir.is_horizontal() {
+          current_dir.turn_right()
         } else {
-          rotate_ccw(current_dir)
+          current_dir.turn_left()
         };
-        run(map, current_pos + new_dir, new_dir, seen);
+        run(map, current_pos + new_dir.to_pos(), new_dir, seen);
       }
       Tile::HorSplitter => {
-        if current_dir.x == 0 {
-          let new_pos_left = current_pos + Pos { x: -1, y: 0 };
-          let new_pos_right = current_pos + Pos { x: 1, y: 0 };
-          let new_dir_left = Pos { x: -1, y: 0 };
-          let new_dir_right = Pos { x: 1, y: 0 };
-          run(map, new_pos_left, new_dir_left, seen);
-          run(map, new_pos_right, new_dir_right, seen);
+        if current_dir.is_vertical() {
+          run(map, current_pos + Direction::W.to_pos(), Direction::W, seen);
+          run(map, current_pos + Direction::E.to_pos(), Direction::E, seen);
         } else {
-          run(map, current_pos + current_dir, current_dir, seen);
+          run(map, current_pos + current_dir.to_pos(), current_dir, seen);
         }
       }
       Tile::VerSplitter => {
-        if current_dir.y == 0 {
-          let new_pos_up = current_pos + Pos { x: 0, y: -1 };
-          let new_pos_down = current_pos + Pos { x: 0, y: 1 };
-          let new_dir_up = Pos { x: 0, y: -1 };
-          let new_dir_down = Pos { x: 0, y: 1 };
-          run(map, new_pos_up, new_dir_up, seen);
-          run(map, new_pos_down, new_dir_down, seen);
+        if current_dir.is_horizontal() {
+          run(map, current_pos + Direction::N.to_pos(), Direction::N, seen);
+          run(map, current_pos + Direction::S.to_pos(), Direction::S, seen);
         } else {
-          run(map, current_pos + current_dir, current_dir, seen);
+          run(map, current_pos + current_dir.to_pos(), current_dir, seen);
         }
       }
     }
   }
 }
 
-fn solve(input: &Grid<Tile>, start_pos: Pos, start_dir: Pos) -> usize {
-  let mut seen: HashSet<(Pos, Pos)> = HashSet::new();
+fn solve(input: &Grid<Tile>, start_pos: Pos, start_dir: Direction) -> usize {
+  let mut seen = BitGrid::for_grid_with_cardinals(input);
   run(input, start_pos, start_dir, &mut seen);
-  let energised: HashSet<&Pos> = seen.iter().map(|(pos, _)| pos).collect();
-  energised.len()
+  seen.count_positions()
+}
+
+// All beams that enter the grid from one of its edges.
+fn edge_starts(input: &Grid<Tile>) -> Vec<(Pos, Direction)> {
+  let (maxx, maxy) = input.max_pos().into();
+  let mut res = vec![];
+  for x in 0..=maxx {
+    res.push((Pos::new(x, 0), Direction::S));
+    res.push((Pos::new(x, maxy), Direction::N));
+  }
+  for y in 0..=maxy {
+    res.push((Pos::new(0, y), Direction::E));
+    res.push((Pos::new(maxx, y), Direction::W));
+  }
+  res
+}
+
+#[derive(Debug)]
+pub struct Contraption {
+  grid: Grid<Tile>,
+  // The number of energised tiles for the beam of part 1, which is also one of
+  // the beams of part 2
+  from_top_left: usize,
+}
+
+// The beam of part 1
+fn top_left() -> (Pos, Direction) {
+  (Pos::new(0, 0), Direction::E)
 }
 
 pub struct Day16;
@@ -94,49 +117,27 @@ impl Day for Day16 {
     parse_grid(parse_tile)(input)
   }
 
+  type Prepared = Contraption;
+
+  fn prepare(grid: Self::Input) -> Result<Self::Prepared, MyErr> {
+    let (pos, dir) = top_left();
+    let from_top_left = solve(&grid, pos, dir);
+    Ok(Contraption { grid, from_top_left })
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    let start_pos = Pos { x: 0, y: 0 };
-    let start_dir = Pos { x: 1, y: 0 };
-    solve(input, start_pos, start_dir)
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    input.from_top_left
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    let (maxx, maxy) = input.iter().fold((0, 0), |(maxx, maxy), (p, _)| {
-      (i32::max(maxx, p.x), i32::max(maxy, p.y))
-    });
-    let mut res = 0;
-    res = usize::max(
-      res,
-      (0..=maxx)
-        .map(|x| solve(input, Pos { x, y: 0 }, Pos { x: 0, y: 1 }))
-        .max()
-        .unwrap_or(0),
-    );
-    res = usize::max(
-      res,
-      (0..=maxx)
-        .map(|x| solve(input, Pos { x, y: maxy }, Pos { x: 0, y: -1 }))
-        .max()
-        .unwrap_or(0),
-    );
-    res = usize::max(
-      res,
-      (0..=maxy)
-        .map(|y| solve(input, Pos { x: 0, y }, Pos { x: 1, y: 0 }))
-        .max()
-        .unwrap_or(0),
-    );
-    res = usize::max(
-      res,
-      (0..=maxy)
-        .map(|y| solve(input, Pos { x: maxx, y }, Pos { x: -1, y: 0 }))
-        .max()
-        .unwrap_or(0),
-    );
-    res
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    edge_starts(&input.grid)
+      .into_iter()
+      .filter(|start| *start != top_left())
+      .map(|(pos, dir)| solve(&input.grid, pos, dir))
+      .fold(input.from_top_left, usize::max)
   }
 }
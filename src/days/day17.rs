@@ -2,36 +2,41 @@ use crate::utils::*;
 use nom::character::complete::one_of;
 use nom::combinator::map as pmap;
 use nom::IResult;
-use pathfinding::directed::astar::astar;
-use std::collections::HashMap;
 
 use crate::days::Day;
+use crate::MyErr;
 
 fn get_new_states(
-  input: &HashMap<Pos, i32>,
-  (pos, dir, count): &(Pos, Pos, u16),
+  input: &Grid<i32>,
+  (pos, dir, count): &(Pos, Direction, u16),
   straight_max: u16,
   turn_min: u16,
-) -> Vec<((Pos, Pos, u16), i32)> {
+) -> Vec<((Pos, Direction, u16), usize)> {
   let mut new_dirs = vec![];
   if *count < straight_max {
     new_dirs.push((*dir, count + 1));
   }
   if *count >= turn_min {
-    new_dirs.push((rotate_cw(*dir), 1));
-    new_dirs.push((rotate_ccw(*dir), 1));
+    new_dirs.push((dir.turn_right(), 1));
+    new_dirs.push((dir.turn_left(), 1));
   }
   let res = new_dirs
     .iter()
     .filter_map(|(d, new_count)| {
-      let new_pos = *pos + *d;
-      let costs = input.get(&new_pos)?;
-      Some(((new_pos, *d, *new_count), *costs))
+      let new_pos = *pos + d.to_pos();
+      let costs = input.get(new_pos)?;
+      Some(((new_pos, *d, *new_count), *costs as usize))
     })
-    .collect::<Vec<((Pos, Pos, u16), i32)>>();
+    .collect::<Vec<((Pos, Direction, u16), usize)>>();
   res
 }
 
+// Every step costs at least 1, so this never overestimates
+fn distance_to_end(input: &Grid<i32>, pos: Pos) -> usize {
+  let end = input.max_pos();
+  ((end.x() - pos.x()) + (end.y() - pos.y())) as usize
+}
+
 pub struct Day17;
 
 impl Day for Day17 {
@@ -43,37 +48,39 @@ impl Day for Day17 {
     }))(input)
   }
 
-  type Output1 = i32;
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
+  type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    let (maxx, maxy) = input.iter().fold((0, 0), |(maxx, maxy), (p, _)| {
-      (i32::max(maxx, p.x), i32::max(maxy, p.y))
-    });
-    let start_state = (Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }, 0);
-    let res = astar(
-      &start_state,
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    let (maxx, maxy) = input.max_pos().into();
+    let start_state = (Pos::new(0, 0), Direction::E, 0);
+    astar(
+      start_state,
       |state| get_new_states(input, state, 3, 0),
-      |_| 1,
-      |(pos, _, _)| pos.x == maxx && pos.y == maxy,
+      |(pos, _, _)| distance_to_end(input, *pos),
+      |(pos, _, _)| pos.x() == maxx && pos.y() == maxy,
     )
-    .unwrap();
-    res.1
+    .goal_cost()
+    .unwrap()
   }
 
-  type Output2 = i32;
+  type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    let (maxx, maxy) = input.iter().fold((0, 0), |(maxx, maxy), (p, _)| {
-      (i32::max(maxx, p.x), i32::max(maxy, p.y))
-    });
-    let start_state = (Pos { x: 0, y: 0 }, Pos { x: 1, y: 0 }, 0);
-    let res = astar(
-      &start_state,
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    let (maxx, maxy) = input.max_pos().into();
+    let start_state = (Pos::new(0, 0), Direction::E, 0);
+    astar(
+      start_state,
       |state| get_new_states(input, state, 10, 4),
-      |_| 1,
-      |(pos, _, c)| pos.x == maxx && pos.y == maxy && *c >= 4,
+      |(pos, _, _)| distance_to_end(input, *pos),
+      |(pos, _, c)| pos.x() == maxx && pos.y() == maxy && *c >= 4,
     )
-    .unwrap();
-    res.1
+    .goal_cost()
+    .unwrap()
   }
 }
@@ -4,9 +4,10 @@ use nom::character::complete::{alpha1, char, newline, u32};
 use nom::multi::{separated_list0, separated_list1};
 use nom::sequence::{pair, tuple};
 use nom::{branch::alt, combinator::map as pmap, IResult};
-use std::collections::HashMap;
+use crate::utils::{blank_line, HashMap, Interval, IntervalBox};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day19;
 
@@ -67,13 +68,9 @@ pub struct Workflow {
   steps: Vec<Action>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Limits {
-  x: (u32, u32),
-  m: (u32, u32),
-  a: (u32, u32),
-  s: (u32, u32),
-}
+// The ratings that are still possible for each category, in the order of
+// `Category`
+type Limits = IntervalBox<u32, 4>;
 
 fn parse_target(input: &str) -> IResult<&str, Target> {
   alt((
@@ -178,42 +175,15 @@ fn action_is_relevant(action: &Action, target: &Target) -> bool {
 }
 
 fn apply_cond(condition: &Condition, limits: &mut Limits, negative: bool) {
-  let mut min = 1;
-  let mut max = 4000;
-  match condition.operator {
+  let axis = condition.category as usize;
+  let (holds, fails) = match condition.operator {
     Operator::GT => {
-      if negative {
-        max = condition.value;
-      } else {
-        min = condition.value + 1;
-      }
-    }
-    Operator::LT => {
-      if negative {
-        min = condition.value;
-      } else {
-        max = condition.value - 1;
-      }
-    }
-  }
-  match condition.category {
-    Category::X => {
-      limits.x.0 = u32::max(limits.x.0, min);
-      limits.x.1 = u32::min(limits.x.1, max);
+      let (below, above) = limits.split_at(axis, condition.value + 1);
+      (above, below)
     }
-    Category::M => {
-      limits.m.0 = u32::max(limits.m.0, min);
-      limits.m.1 = u32::min(limits.m.1, max);
-    }
-    Category::A => {
-      limits.a.0 = u32::max(limits.a.0, min);
-      limits.a.1 = u32::min(limits.a.1, max);
-    }
-    Category::S => {
-      limits.s.0 = u32::max(limits.s.0, min);
-      limits.s.1 = u32::min(limits.s.1, max);
-    }
-  }
+    Operator::LT => limits.split_at(axis, condition.value),
+  };
+  *limits = if negative { fails } else { holds };
 }
 
 fn get_options(workflows: &[Workflow], target: &Target, limits: &Limits) -> usize {
@@ -225,7 +195,7 @@ fn get_options(workflows: &[Workflow], target: &Target, limits: &Limits) -> usiz
         .iter()
         .positions(|a| action_is_relevant(a, target))
         .map(|i| {
-          let mut new_limits = limits.clone();
+          let mut new_limits = *limits;
           for prev in wf.steps.iter().take(i) {
             match prev {
               Action::Send(_) => {
@@ -243,10 +213,7 @@ fn get_options(workflows: &[Workflow], target: &Target, limits: &Limits) -> usiz
             Action::Send(_) => {}
           }
           if wf.name == "in" {
-            (new_limits.x.1 - new_limits.x.0 + 1) as usize
-              * (new_limits.m.1 - new_limits.m.0 + 1) as usize
-              * (new_limits.a.1 - new_limits.a.0 + 1) as usize
-              * (new_limits.s.1 - new_limits.s.0 + 1) as usize
+            new_limits.volume()
           } else {
             get_options(workflows, &Target::Other(wf.name.clone()), &new_limits)
           }
@@ -261,14 +228,20 @@ impl Day for Day19 {
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
     let (input, wfs) = separated_list1(newline, parse_workflow)(input)?;
-    let (input, _) = pair(newline, newline)(input)?;
+    let (input, _) = blank_line(input)?;
     let (input, parts) = separated_list1(newline, parse_part)(input)?;
     Ok((input, (wfs, parts)))
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = u32;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     let (wfs, parts) = input;
     let workflows: HashMap<String, Vec<Action>> = wfs
       .iter()
@@ -283,14 +256,9 @@ impl Day for Day19 {
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let (wfs, _) = input;
-    let limits = Limits {
-      x: (1, 4000),
-      m: (1, 4000),
-      a: (1, 4000),
-      s: (1, 4000),
-    };
+    let limits = IntervalBox([Interval::inclusive(1, 4000); 4]);
     get_options(wfs, &Target::Accept, &limits)
   }
 }
@@ -4,11 +4,12 @@ use nom::{
   branch::alt, bytes::complete::tag, character::complete::alpha1, combinator::map as pmap,
   sequence::pair, IResult,
 };
-use num::integer::lcm;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::VecDeque;
+use crate::utils::{first_common_time, HashMap, HashSet};
 use std::str::FromStr;
 
 use crate::days::Day;
+use crate::MyErr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Module {
@@ -46,15 +47,18 @@ fn parse_line(input: &str) -> IResult<&str, (Module, Vec<String>)> {
   ))
 }
 
+// Presses the button once. Returns the number of high pulses that `watched`
+// sent.
 fn run(
   mods: &HashMap<Module, Vec<Module>>,
   state: &mut HashMap<Module, bool>,
   high_counter: &mut usize,
   low_counter: &mut usize,
   conjunction_state: &mut HashMap<Module, HashMap<Module, bool>>,
+  watched: &Module,
 ) -> usize {
   let mut q: VecDeque<(&Module, &Module, bool)> = VecDeque::new();
-  let mut rx_counter = 0;
+  let mut watched_counter = 0;
   q.push_back((&Module::Broadcaster, &Module::Broadcaster, false));
   while let Some((from, to, signal)) = q.pop_front() {
     if signal {
@@ -62,38 +66,36 @@ fn run(
     } else {
       *low_counter += 1;
     }
-    match to {
+    let sent = match to {
       Module::FlipFlop(_) => {
-        if !signal {
-          let s = state.get(to).unwrap();
-          let new_signal = !s;
-          *state.get_mut(to).unwrap() = new_signal;
-          for m2 in mods.get(to).unwrap() {
-            q.push_back((to, m2, new_signal));
-          }
+        if signal {
+          None
+        } else {
+          let s = state.get_mut(to).unwrap();
+          *s = !*s;
+          Some(*s)
         }
       }
       Module::Conjunction(_) => {
         let memory = conjunction_state.get_mut(to).unwrap();
         *memory.get_mut(from).unwrap() = signal;
-        let new_signal = memory.values().any(|x| !x);
-        for m2 in mods.get(to).unwrap() {
-          q.push_back((to, m2, new_signal));
-        }
+        Some(memory.values().any(|x| !x))
       }
-      Module::Broadcaster => {
-        for m2 in mods.get(to).unwrap() {
-          q.push_back((to, m2, signal));
-        }
+      Module::Broadcaster => Some(signal),
+      Module::Output(_) => None,
+    };
+    if let Some(new_signal) = sent {
+      // Counted here rather than when the pulse arrives, as the modules it is
+      // sent to may not be part of the network
+      if new_signal && to == watched {
+        watched_counter += 1;
       }
-      Module::Output(s) => {
-        if s == "rx" && !signal {
-          rx_counter += 1;
-        }
+      for m2 in mods.get(to).unwrap() {
+        q.push_back((to, m2, new_signal));
       }
     }
   }
-  rx_counter
+  watched_counter
 }
 
 fn extend_subnetwork(
@@ -112,7 +114,7 @@ fn get_subnetwork(
   mods: &HashMap<Module, Vec<Module>>,
   target: &Module,
 ) -> HashMap<Module, Vec<Module>> {
-  let mut parents = HashSet::new();
+  let mut parents = HashSet::default();
   extend_subnetwork(mods, target, &mut parents);
   parents.insert(target.clone());
   mods.iter()
@@ -132,11 +134,14 @@ fn get_subnetwork(
     .collect()
 }
 
-fn find_cycle(mods: &HashMap<Module, Vec<Module>>) -> usize {
-  let init_state: HashMap<Module, bool> = mods.iter().map(|(m, _)| (m.clone(), false)).collect();
+// The first press on which `target` sends a high pulse, and how many presses
+// it takes before it does so again. Assumes that it does so once per cycle of
+// the states, like it does in every input.
+fn find_cycle(mods: &HashMap<Module, Vec<Module>>, target: &Module) -> (i128, i128) {
+  let init_state: HashMap<Module, bool> = mods.keys().map(|m| (m.clone(), false)).collect();
   let init_conjunction_state: HashMap<Module, HashMap<Module, bool>> = mods
-    .iter()
-    .filter_map(|(m, _)| {
+    .keys()
+    .filter_map(|m| {
       if let Module::Conjunction(_) = m {
         Some((
           m.clone(),
@@ -155,30 +160,38 @@ fn find_cycle(mods: &HashMap<Module, Vec<Module>>) -> usize {
   let mut high_counter = 0;
   let mut low_counter = 0;
 
-  run(
+  let first_highs = run(
     mods,
     &mut state,
     &mut high_counter,
     &mut low_counter,
     &mut conjunction_state,
+    target,
   );
 
   let check_state = state.clone();
   let check_c_state = conjunction_state.clone();
 
+  // The states repeat from the first press on, so the first press counts too
+  let mut first_high = (first_highs > 0).then_some(1);
   for i in 1.. {
-    run(
+    let highs = run(
       mods,
       &mut state,
       &mut high_counter,
       &mut low_counter,
       &mut conjunction_state,
+      target,
     );
+    if highs > 0 && first_high.is_none() {
+      first_high = Some(i + 1);
+    }
     if state == check_state && conjunction_state == check_c_state {
-      return i;
+      let first_high = first_high.expect("The module never sends a high pulse");
+      return (first_high as i128, i as i128);
     }
   }
-  0
+  unreachable!()
 }
 
 pub struct Day20;
@@ -194,9 +207,15 @@ impl Day for Day20 {
     ))
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
     let mods: HashMap<Module, Vec<Module>> = input
       .iter()
       .map(|(m, ss)| {
@@ -221,10 +240,10 @@ impl Day for Day20 {
       .collect();
 
     let mut state: HashMap<Module, bool> =
-      input.iter().map(|(m, _)| (m.clone(), false)).collect();
+      input.keys().map(|m| (m.clone(), false)).collect();
     let mut conjunction_state: HashMap<Module, HashMap<Module, bool>> = mods
-      .iter()
-      .filter_map(|(m, _)| {
+      .keys()
+      .filter_map(|m| {
         if let Module::Conjunction(_) = m {
           Some((
             m.clone(),
@@ -248,6 +267,7 @@ impl Day for Day20 {
         &mut high_counter,
         &mut low_counter,
         &mut conjunction_state,
+        &Module::Broadcaster,
       );
     }
     high_counter * low_counter
@@ -255,7 +275,7 @@ impl Day for Day20 {
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
     let mods: HashMap<Module, Vec<Module>> = input
       .iter()
       .map(|(m, ss)| {
@@ -279,13 +299,14 @@ impl Day for Day20 {
       })
       .collect();
 
-    mods.iter()
+    let cycles = mods
+      .iter()
       .filter(|(_, ms)| ms.contains(&Module::Output("rx".to_owned())))
       .flat_map(|(m, _)| {
         mods.iter()
           .filter_map(|(m2, ms)| if ms.contains(m) { Some(m2) } else { None })
       })
-      .map(|m| find_cycle(&get_subnetwork(&mods, m)))
-      .fold(1, lcm)
+      .map(|m| find_cycle(&get_subnetwork(&mods, m), m));
+    first_common_time(cycles).expect("The modules never all send a high pulse on the same press") as usize
   }
 }
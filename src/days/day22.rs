@@ -1,52 +1,26 @@
-use std::collections::{HashMap, HashSet};
+use crate::utils::{Cuboid, HashMap, HashSet, HeightMap, Point3};
 
 use nom::character::complete::{char, i32, newline};
 use nom::multi::separated_list0;
 use nom::{sequence::tuple, IResult};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day22;
 
-fn parse_line(input: &str) -> IResult<&str, Brick> {
-  let (input, ((x1, _, y1, _, z1), _, (x2, _, y2, _, z2))) = tuple((
-    tuple((i32, char(','), i32, char(','), i32)),
-    char('~'),
-    tuple((i32, char(','), i32, char(','), i32)),
-  ))(input)?;
-  Ok((input, ((x1, y1, z1), (x2, y2, z2))))
+fn parse_corner(input: &str) -> IResult<&str, Point3<i32>> {
+  let (input, (x, _, y, _, z)) = tuple((i32, char(','), i32, char(','), i32))(input)?;
+  Ok((input, Point3::new(x, y, z)))
 }
 
-type Pos3 = (i32, i32, i32);
-type Brick = (Pos3, Pos3);
-
-// I think this is probably unnecessary, and you don't actually need to simulate
-// the falling. Knowing which bricks are above which should be enough. But oh well,
-// can't be bothered to change that now.
-fn fall_step(input: &[Brick]) -> Vec<Brick> {
-  input
-    .iter()
-    .map(|((x1, y1, z1), (x2, y2, z2))| {
-      let z_below: i32 = *input
-        .iter()
-        .filter_map(|((x3, y3, z3), (x4, y4, z4))| {
-          assert!(x3 <= x4);
-          assert!(y3 <= y4);
-          assert!(z3 <= z4);
-          assert!(x1 <= x2);
-          assert!(y1 <= y2);
-          assert!(z1 <= z2);
-          (z4 < z1 && x3 <= x2 && x4 >= x1 && y3 <= y2 && y4 >= y1).then_some(*z4)
-        })
-        .max()
-        .get_or_insert(0);
-      ((*x1, *y1, z_below + 1), (*x2, *y2, z_below + 1 + (z2 - z1)))
-    })
-    .collect()
+fn parse_line(input: &str) -> IResult<&str, Cuboid> {
+  let (input, (a, _, b)) = tuple((parse_corner, char('~'), parse_corner))(input)?;
+  Ok((input, Cuboid::new(a, b)))
 }
 
 fn get_parents_count(leans_on: &HashMap<usize, Vec<usize>>, i: &usize) -> usize {
-  let mut removed: HashSet<usize> = HashSet::new();
+  let mut removed: HashSet<usize> = HashSet::default();
   removed.insert(*i);
   loop {
     let c = removed.len();
@@ -62,44 +36,37 @@ fn get_parents_count(leans_on: &HashMap<usize, Vec<usize>>, i: &usize) -> usize
   removed.len() - 1
 }
 
-fn create_leaning_on_tree(input: &[Brick]) -> HashMap<usize, Vec<usize>> {
-  let mut final_state: Vec<Brick> = input.to_owned();
-  loop {
-    let new_state = fall_step(&final_state);
-    if new_state == final_state {
-      final_state = new_state;
-      break;
-    }
-    final_state = new_state;
-  }
-  let mut leans_on: HashMap<usize, Vec<usize>> = HashMap::new();
-  for (i, ((x1, y1, z1), (x2, y2, _))) in final_state.iter().enumerate() {
-    leans_on.insert(
-      i,
-      final_state
-        .iter()
-        .enumerate()
-        .filter(|(_, ((x3, y3, _), (x4, y4, z4)))| {
-          *z4 == z1 - 1 && x3 <= x2 && x4 >= x1 && y3 <= y2 && y4 >= y1
-        })
-        .map(|(j, _)| j)
-        .collect(),
-    );
+// Lets all bricks fall from the bottom up, and returns which bricks each brick
+// rests on
+fn create_leaning_on_tree(input: &[Cuboid]) -> HashMap<usize, Vec<usize>> {
+  let mut order: Vec<usize> = (0..input.len()).collect();
+  order.sort_by_key(|i| input[*i].bottom());
+  let mut height_map = HeightMap::new();
+  let mut leans_on: HashMap<usize, Vec<usize>> = HashMap::default();
+  for i in order {
+    let (_, supports) = height_map.fall(&input[i], i, 1);
+    leans_on.insert(i, supports);
   }
   leans_on
 }
 
 impl Day for Day22 {
-  type Input = Vec<Brick>;
+  type Input = Vec<Cuboid>;
 
   fn parse(input: &str) -> IResult<&str, Self::Input> {
     separated_list0(newline, parse_line)(input)
   }
 
+  // Which bricks each brick rests on once they're settled
+  type Prepared = HashMap<usize, Vec<usize>>;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(create_leaning_on_tree(&input))
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    let leans_on = create_leaning_on_tree(input);
+  fn part_1(leans_on: &Self::Prepared) -> Self::Output1 {
     leans_on
       .iter()
       .enumerate()
@@ -113,12 +80,11 @@ impl Day for Day22 {
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    let leans_on = create_leaning_on_tree(input);
+  fn part_2(leans_on: &Self::Prepared) -> Self::Output2 {
     leans_on
       .iter()
       .enumerate()
-      .map(|(i, _)| get_parents_count(&leans_on, &i))
+      .map(|(i, _)| get_parents_count(leans_on, &i))
       .sum()
   }
 }
@@ -3,9 +3,9 @@ use nom::branch::alt;
 use nom::character::complete::char;
 use nom::combinator::map as pmap;
 use nom::IResult;
-use std::collections::{HashMap, HashSet};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day23;
 
@@ -13,54 +13,34 @@ pub struct Day23;
 pub enum Tile {
   Path,
   Forest,
-  Slope(Pos),
+  Slope(Direction),
 }
 
 fn parse_tile(input: &str) -> IResult<&str, Tile> {
   alt((
     pmap(char('.'), |_| Tile::Path),
     pmap(char('#'), |_| Tile::Forest),
-    pmap(char('^'), |_| Tile::Slope(Pos { x: 0, y: -1 })),
-    pmap(char('>'), |_| Tile::Slope(Pos { x: 1, y: 0 })),
-    pmap(char('v'), |_| Tile::Slope(Pos { x: 0, y: 1 })),
-    pmap(char('<'), |_| Tile::Slope(Pos { x: -1, y: 0 })),
+    pmap(parse_direction, Tile::Slope),
   ))(input)
 }
 
-fn step(grid: &Grid<Tile>, pos: &Pos, goal: &Pos, seen: &HashSet<Pos>) -> usize {
+fn step(grid: &Grid<Tile>, pos: &Pos, goal: &Pos, seen: &BitGrid) -> usize {
   if pos == goal {
     0
   } else {
     let mut new_seen = seen.clone();
     new_seen.insert(*pos);
-    let new_poses = match grid.get(pos) {
+    let new_poses = match grid.get(*pos) {
       None => vec![],
       Some(Tile::Forest) => vec![],
-      Some(Tile::Path) => vec![
-        Pos {
-          x: pos.x + 1,
-          y: pos.y,
-        },
-        Pos {
-          x: pos.x - 1,
-          y: pos.y,
-        },
-        Pos {
-          x: pos.x,
-          y: pos.y + 1,
-        },
-        Pos {
-          x: pos.x,
-          y: pos.y - 1,
-        },
-      ],
-      Some(Tile::Slope(p)) => vec![*pos + *p],
+      Some(Tile::Path) => Direction::cardinals().map(|d| *pos + d.to_pos()).collect(),
+      Some(Tile::Slope(d)) => vec![*pos + d.to_pos()],
     };
     *new_poses
       .iter()
       .filter(|p| {
-        !new_seen.contains(p)
-          && matches!(grid.get(p), Some(Tile::Path) | Some(Tile::Slope(_)))
+        !new_seen.contains(**p)
+          && matches!(grid.get(**p), Some(Tile::Path) | Some(Tile::Slope(_)))
       })
       .map(|p| step(grid, p, goal, &new_seen))
       .max()
@@ -70,71 +50,63 @@ fn step(grid: &Grid<Tile>, pos: &Pos, goal: &Pos, seen: &HashSet<Pos>) -> usize
 }
 
 fn longest_path(grid: &Grid<Tile>, start: &Pos, end: &Pos) -> usize {
-  let mut seen: HashSet<Pos> = HashSet::new();
+  let mut seen = BitGrid::for_grid(grid);
   seen.insert(*start);
   step(grid, start, end, &seen)
 }
 
-fn step2(
-  pos: &Pos,
-  goal: &Pos,
-  seen: &HashSet<Pos>,
-  successors: &HashMap<Pos, Vec<(Pos, usize)>>,
-) -> usize {
-  if pos == goal {
+fn step2(graph: &Graph<Pos>, id: NodeId, goal: NodeId, seen: &mut Vec<bool>) -> usize {
+  if id == goal {
     0
   } else {
-    let mut new_seen = seen.clone();
-    new_seen.insert(*pos);
-    *successors
-      .get(pos)
-      .unwrap()
-      .iter()
-      .filter(|(p, _)| !new_seen.contains(p))
-      .map(|(p, d)| step2(p, goal, &new_seen, successors) + *d)
-      .max()
-      .get_or_insert(0)
+    seen[id] = true;
+    let mut longest = 0;
+    for (n, d) in graph.neighbours(id) {
+      if !seen[n] {
+        longest = longest.max(step2(graph, n, goal, seen) + d);
+      }
+    }
+    seen[id] = false;
+    longest
   }
 }
 
-fn longest_path2(start: &Pos, end: &Pos, successors: &HashMap<Pos, Vec<(Pos, usize)>>) -> usize {
-  let seen: HashSet<Pos> = HashSet::new();
-  step2(start, end, &seen, successors)
-}
-
-fn create_successor_map<F>(grid: &Grid<Tile>, f: F) -> HashMap<Pos, Vec<(Pos, usize)>>
-where
-  F: Fn(&Pos) -> Vec<Pos>,
-{
-  let mut res = grid
-    .iter()
-    .filter_map(|(pos, t)| match t {
-      Tile::Forest => None,
-      _ => Some((*pos, f(pos).iter().map(|p| (*p, 1)).collect())),
-    })
-    .collect();
-  while simplify_step(&mut res) {}
-  res
+fn longest_path2(graph: &Graph<Pos>, start: &Pos, end: &Pos) -> usize {
+  let mut seen = vec![false; graph.len()];
+  step2(graph, graph.id(start).unwrap(), graph.id(end).unwrap(), &mut seen)
 }
 
-fn simplify_step(successors: &mut HashMap<Pos, Vec<(Pos, usize)>>) -> bool {
-  let mut changed = false;
-  for (p, _) in successors.clone() {
-    let nexts = successors.get(&p).unwrap();
-    if nexts.len() == 2 {
-      let (p1, n1) = nexts[0];
-      let (p2, n2) = nexts[1];
-      let s1 = successors.get_mut(&p1).unwrap();
-      s1.retain(|(q, _)| *q != p);
-      s1.push((p2, n2 + n1));
-      let s2 = successors.get_mut(&p2).unwrap();
-      s2.retain(|(q, _)| *q != p);
-      s2.push((p1, n2 + n1));
-      successors.remove(&p);
-      changed = true;
+// The crossings of the trails, and how long the trails between them are,
+// ignoring the slopes
+fn create_trail_graph(grid: &Grid<Tile>) -> Graph<Pos> {
+  let mut graph = Graph::undirected();
+  for (pos, _) in grid.iter().filter(|(_, t)| **t != Tile::Forest) {
+    graph.add_node(pos);
+    // Only look forward, the other neighbours have added their edge already
+    for d in [Direction::E, Direction::S] {
+      let next = pos + d.to_pos();
+      if matches!(grid.get(next), Some(Tile::Path) | Some(Tile::Slope(_))) {
+        graph.add_edge(pos, next, 1);
+      }
     }
   }
-  changed
+  graph.contract_paths()
+}
+
+#[derive(Debug)]
+pub struct Trails {
+  grid: Grid<Tile>,
+  // The entrance and exit
+  start: Pos,
+  end: Pos,
+}
+
+// The only path tile in row y
+fn path_in_row(grid: &Grid<Tile>, y: i32) -> Option<Pos> {
+  grid
+    .iter()
+    .find(|(p, t)| p.y() == y && **t == Tile::Path)
+    .map(|(p, _)| p)
 }
 
 impl Day for Day23 {
@@ -144,57 +116,27 @@ impl Day for Day23 {
     parse_grid(parse_tile)(input)
   }
 
+  type Prepared = Trails;
+
+  fn prepare(grid: Self::Input) -> Result<Self::Prepared, MyErr> {
+    let start = path_in_row(&grid, 0);
+    let end = path_in_row(&grid, grid.max_pos().y());
+    match (start, end) {
+      (Some(start), Some(end)) => Ok(Trails { grid, start, end }),
+      _ => Err(MyErr::Other("The trails need an entrance at the top and an exit at the bottom".to_owned())),
+    }
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    let (start, _) = input
-      .iter()
-      .find(|(p, t)| p.y == 0 && **t == Tile::Path)
-      .unwrap();
-    let (_, maxy) = grid_max_dims(input);
-    let (end, _) = input
-      .iter()
-      .find(|(p, t)| p.y == maxy && **t == Tile::Path)
-      .unwrap();
-    longest_path(input, start, end)
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    longest_path(&input.grid, &input.start, &input.end)
   }
 
   type Output2 = usize;
 
-  fn part_2(input: &Self::Input) -> Self::Output2 {
-    let (start, _) = input
-      .iter()
-      .find(|(p, t)| p.y == 0 && **t == Tile::Path)
-      .unwrap();
-    let (_, maxy) = grid_max_dims(input);
-    let (end, _) = input
-      .iter()
-      .find(|(p, t)| p.y == maxy && **t == Tile::Path)
-      .unwrap();
-
-    let successors: HashMap<Pos, Vec<(Pos, usize)>> = create_successor_map(input, |pos| {
-      [
-        Pos {
-          x: pos.x + 1,
-          y: pos.y,
-        },
-        Pos {
-          x: pos.x - 1,
-          y: pos.y,
-        },
-        Pos {
-          x: pos.x,
-          y: pos.y + 1,
-        },
-        Pos {
-          x: pos.x,
-          y: pos.y - 1,
-        },
-      ]
-      .into_iter()
-      .filter(|p| matches!(input.get(p), Some(Tile::Path) | Some(Tile::Slope(_))))
-      .collect::<Vec<Pos>>()
-    });
-    longest_path2(start, end, &successors)
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    let graph = create_trail_graph(&input.grid);
+    longest_path2(&graph, &input.start, &input.end)
   }
 }
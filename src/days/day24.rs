@@ -1,25 +1,118 @@
+use crate::utils::{cross, lines, numbers, rational, Intersection, Line, Matrix, Point3, Rational};
+use itertools::Itertools;
+use nom::character::complete::{char, space1};
+use nom::combinator::map_opt;
+use nom::sequence::{pair, separated_pair, tuple};
 use nom::IResult;
+use num::{Signed, ToPrimitive, Zero};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day24;
 
+#[derive(Debug, Clone, Copy)]
+pub struct Hailstone {
+  pos: Point3<i64>,
+  vel: Point3<i64>,
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point3<i64>> {
+  map_opt(numbers(pair(char(','), space1)), |v: Vec<i64>| {
+    <[i64; 3]>::try_from(v).ok().map(Point3::from)
+  })(input)
+}
+
+fn parse_hailstone(input: &str) -> IResult<&str, Hailstone> {
+  let (input, (pos, vel)) = separated_pair(parse_point, tuple((space1, char('@'), space1)), parse_point)(input)?;
+  Ok((input, Hailstone { pos, vel }))
+}
+
+// The number of pairs of which the paths cross in the test area in the future,
+// ignoring the z axis
+fn count_crossings(input: &[Hailstone], min: i64, max: i64) -> usize {
+  let paths: Vec<Line<2>> = input
+    .iter()
+    .map(|h| Line::new([h.pos.x(), h.pos.y()].into(), [h.vel.x(), h.vel.y()].into()))
+    .collect();
+  let (min, max) = (rational(min), rational(max));
+  paths
+    .iter()
+    .tuple_combinations()
+    .filter(|(a, b)| match a.intersect(b) {
+      Intersection::Point { at, t, u } => {
+        !t.is_negative() && !u.is_negative() && at.iter().all(|c| *c >= min && *c <= max)
+      }
+      _ => false,
+    })
+    .count()
+}
+
+fn to_rationals(p: Point3<i64>) -> [Rational; 3] {
+  p.0.map(rational)
+}
+
+// The rock at p with velocity v hits hailstone i when (p - pi) x (v - vi) = 0.
+// Subtracting that for two hailstones cancels the p x v term, which leaves
+// p x (vj - vi) + (pj - pi) x v = pj x vj - pi x vi: three linear equations.
+fn rock_equations(a: &Hailstone, b: &Hailstone) -> Vec<(Vec<Rational>, Rational)> {
+  let d = to_rationals(b.vel - a.vel);
+  let e = to_rationals(b.pos - a.pos);
+  let (pa, va, pb, vb) = (to_rationals(a.pos), to_rationals(a.vel), to_rationals(b.pos), to_rationals(b.vel));
+  let rhs: Vec<Rational> = cross(&pb, &vb).iter().zip(cross(&pa, &va).iter()).map(|(x, y)| x - y).collect();
+  let zero = Rational::zero();
+  let coefficients = [
+    [zero.clone(), d[2].clone(), -&d[1], zero.clone(), -&e[2], e[1].clone()],
+    [-&d[2], zero.clone(), d[0].clone(), e[2].clone(), zero.clone(), -&e[0]],
+    [d[1].clone(), -&d[0], zero.clone(), -&e[1], e[0].clone(), zero.clone()],
+  ];
+  coefficients.into_iter().map(|c| c.to_vec()).zip(rhs).collect()
+}
+
 impl Day for Day24 {
-  type Input = String;
+  type Input = Vec<Hailstone>;
+
+  fn parse(input: &str) -> IResult<&str, Self::Input> {
+    lines(parse_hailstone)(input)
+  }
+
+  type Prepared = Self::Input;
 
-  fn parse(_input: &str) -> IResult<&str, Self::Input> {
-    unimplemented!("parser")
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
   }
 
-  type Output1 = String;
+  type Output1 = usize;
 
-  fn part_1(_input: &Self::Input) -> Self::Output1 {
-    unimplemented!("part_1")
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    count_crossings(input, 200000000000000, 400000000000000)
   }
 
-  type Output2 = String;
+  type Output2 = i64;
 
-  fn part_2(_input: &Self::Input) -> Self::Output2 {
-    unimplemented!("part_2")
+  fn part_2(input: &Self::Prepared) -> Self::Output2 {
+    // Two pairs give six equations for the six unknowns, unless some of the
+    // hailstones happen to be parallel
+    input
+      .iter()
+      .tuple_combinations()
+      .find_map(|(a, b, c)| {
+        let equations: Vec<_> = rock_equations(a, b).into_iter().chain(rock_equations(a, c)).collect();
+        let mut m = Matrix::zeros(6, 6);
+        for (r, (coefficients, _)) in equations.iter().enumerate() {
+          for (col, coefficient) in coefficients.iter().enumerate() {
+            m[(r, col)] = coefficient.clone();
+          }
+        }
+        let rhs: Vec<Rational> = equations.into_iter().map(|(_, rhs)| rhs).collect();
+        m.solve(&rhs).ok()
+      })
+      .expect("Every triple of hailstones has parallel ones")[..3]
+      .iter()
+      .map(|c| {
+        assert!(c.is_integer(), "The rock doesn't start at a whole position: {}", c);
+        c.to_integer().to_i64().expect("The rock starts too far away")
+      })
+      .sum()
   }
 }
@@ -1,9 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use crate::utils::{bfs, Graph, NodeId, Search};
 use itertools::Itertools;
-use pathfinding::directed::dijkstra::dijkstra;
+use log::debug;
 use nom::{IResult, character::complete::{alpha1, space1, newline}, bytes::complete::tag, multi::separated_list1};
 
 use crate::days::Day;
+use crate::MyErr;
 
 pub struct Day25;
 
@@ -14,18 +15,18 @@ fn parse_line(input: &str) -> IResult<&str, (String, Vec<String>)> {
   Ok((input, (key.to_owned(), connections.into_iter().map(|v| v.to_owned()).collect())))
 }
 
-fn count_nodes(hm: &HashMap<&str, HashSet<&str>>, ignored: &HashSet<(&str, &str)>, start: &str) -> usize {
-  let mut seen: HashSet<&str> = HashSet::new();
-  seen.insert(start);
-  let mut explore: Vec<&str> = vec![start];
-  while let Some(n) = explore.pop() {
-    for n2 in hm.get(n).unwrap().iter().filter(|n2| !ignored.contains(&(n, n2)) && !ignored.contains(&(n2, n))) {
-      if seen.insert(n2) {
-        explore.push(n2);
-      }
-    }
-  }
-  seen.len()
+// The number of components that can be reached from the start without the
+// ignored wires
+fn count_nodes(graph: &Graph<&str>, ignored: &[(NodeId, NodeId)], start: NodeId) -> usize {
+  let is_ignored = |n1: NodeId, n2: NodeId| ignored.iter().any(|w| *w == (n1, n2) || *w == (n2, n1));
+  let successors = |n: &NodeId| {
+    let n = *n;
+    graph
+      .neighbours(n)
+      .filter(move |(m, _)| !is_ignored(n, *m))
+      .map(|(m, _)| m)
+  };
+  bfs(start, successors, |_| false).costs.len()
 }
 
 impl Day for Day25 {
@@ -35,40 +36,35 @@ impl Day for Day25 {
     separated_list1(newline, parse_line)(input)
   }
 
+  type Prepared = Self::Input;
+
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr> {
+    Ok(input)
+  }
+
   type Output1 = usize;
 
-  fn part_1(input: &Self::Input) -> Self::Output1 {
-    let mut hm: HashMap<&str, HashSet<&str>> = HashMap::new();
-    let mut connections: HashSet<(&str, &str)> = HashSet::new();
+  fn part_1(input: &Self::Prepared) -> Self::Output1 {
+    let mut graph: Graph<&str> = Graph::undirected();
     for (k1, vals) in input {
       for k2 in vals {
-        let k1e = hm.entry(k1).or_insert(HashSet::new());
-        k1e.insert(k2);
-        let k2e = hm.entry(k2).or_insert(HashSet::new());
-        k2e.insert(k1);
-        connections.insert((k1, k2));
+        graph.add_edge(k1, k2, 1);
       }
     }
-    let nodes: Vec<&str> = hm.keys().map(|x| *x).collect();
-    let mut counts = HashMap::new();
-    for n in nodes.iter() {
-      counts.insert(n, 0);
-    }
-    for i in 0..nodes.len() {
-      for j in 0..nodes.len() {
-        let n1 = nodes[i];
-        let n2 = nodes[j];
-        let (path, _) = dijkstra(&n1,|n| hm.get(n).unwrap().iter().map(|n3| (*n3, 1)), |n| n == &n2).unwrap();
-        for n in path {
-          let c = counts.get_mut(&n).unwrap();
-          *c += 1;
+    let mut counts = vec![0; graph.len()];
+    // Count how often every node is on a shortest path between two nodes
+    for n1 in graph.nodes() {
+      let paths = Search::with_paths().bfs(n1, |n| graph.neighbours(*n).map(|(m, _)| m), |_| false);
+      for n2 in graph.nodes() {
+        for n in paths.path_to(&n2).unwrap() {
+          counts[n] += 1;
         }
       }
     }
-    let top6: Vec<&str> = counts.into_iter().sorted_by(|(_,c1),(_,c2)| c2.cmp(c1)).take(6).map(|(s,_)| *s).collect::<Vec<&str>>();
-    
-    let start_node = &input.first().unwrap().0;
-    let total_count = count_nodes(&hm, &HashSet::new(), &start_node);
+    let top6: Vec<NodeId> = graph.nodes().sorted_by(|n1, n2| counts[*n2].cmp(&counts[*n1])).take(6).collect();
+
+    let start_node = graph.id(&input.first().unwrap().0.as_str()).unwrap();
+    let total_count = count_nodes(&graph, &[], start_node);
     let mut options = vec![];
     for i in 0..top6.len() {
       for j in i+1..top6.len() {
@@ -79,10 +75,9 @@ impl Day for Day25 {
     for i in 0..options.len() {
       for j in i+1..options.len() {
         for k in j+1..options.len() {
-          let ignored: HashSet<(&str, &str)> = HashSet::from_iter([options[i], options[j], options[k]]);
-          let new_count = count_nodes(&hm, &ignored, &start_node);
+          let new_count = count_nodes(&graph, &[options[i], options[j], options[k]], start_node);
           if new_count != total_count {
-            println!("new count: {}", new_count);
+            debug!("new count: {}", new_count);
             return new_count * (total_count - new_count);
           }
         }
@@ -93,7 +88,7 @@ impl Day for Day25 {
 
   type Output2 = usize;
 
-  fn part_2(_input: &Self::Input) -> Self::Output2 {
+  fn part_2(_input: &Self::Prepared) -> Self::Output2 {
     0
   }
 }
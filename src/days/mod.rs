@@ -1,8 +1,10 @@
-use crate::parser::MyErr;
-use crate::Instant;
+use crate::logger::{set_stage, Stage};
+use crate::MyErr;
+use crate::utils::read_drawing;
 use nom::IResult;
 use std::fmt::Display;
 use std::fs::read_to_string;
+use std::time::Instant;
 
 pub mod day01;
 pub mod day02;
@@ -30,18 +32,103 @@ pub mod day23;
 pub mod day24;
 pub mod day25;
 
+// Answers that span multiple lines, like a drawing of a grid, are printed as an
+// indented block below the part header, so they don't get mangled. If the
+// drawing spells out letters, those are printed in the header.
+fn print_answer<A: Display>(part: usize, answer: A) {
+  let answer = answer.to_string();
+  if answer.contains('\n') {
+    match read_drawing(&answer) {
+      Some(letters) => println!("Part {}: {}", part, letters),
+      None => println!("Part {}:", part),
+    }
+    for line in answer.lines() {
+      println!("  {}", line);
+    }
+  } else {
+    println!("Part {}: {}", part, answer);
+  }
+}
+
+// Solves a part, and prints its answer and how long it took. Days from plugins
+// are run through this as well, so they look the same.
+pub(crate) fn run_part<A: Display, F: FnOnce() -> A>(part: usize, solve: F) {
+  set_stage(Stage::Part(part));
+  let before = Instant::now();
+  print_answer(part, solve());
+  println!("Part {} took {}s", part, before.elapsed().as_secs_f32());
+}
+
+/// The entry points of a day's solution, so they can be looked up by day number.
+pub struct Solution {
+  pub run: fn(&str),
+  pub solve: fn(&str) -> Result<(String, String), MyErr>,
+  pub solve_part: fn(&str, usize) -> Result<String, MyErr>,
+}
+
+impl Solution {
+  pub fn of<D: Day>() -> Solution {
+    Solution {
+      run: D::run_day,
+      solve: D::solve,
+      solve_part: D::solve_part,
+    }
+  }
+}
+
+/// Panics if you provide a value outside the range of 1 to 25
+pub fn solution(day: usize) -> Solution {
+  // I'd like to do this with a macro, but I'm not sure how to do it.
+  match day {
+    1 => Solution::of::<day01::Day01>(),
+    2 => Solution::of::<day02::Day02>(),
+    3 => Solution::of::<day03::Day03>(),
+    4 => Solution::of::<day04::Day04>(),
+    5 => Solution::of::<day05::Day05>(),
+    6 => Solution::of::<day06::Day06>(),
+    7 => Solution::of::<day07::Day07>(),
+    8 => Solution::of::<day08::Day08>(),
+    9 => Solution::of::<day09::Day09>(),
+    10 => Solution::of::<day10::Day10>(),
+    11 => Solution::of::<day11::Day11>(),
+    12 => Solution::of::<day12::Day12>(),
+    13 => Solution::of::<day13::Day13>(),
+    14 => Solution::of::<day14::Day14>(),
+    15 => Solution::of::<day15::Day15>(),
+    16 => Solution::of::<day16::Day16>(),
+    17 => Solution::of::<day17::Day17>(),
+    18 => Solution::of::<day18::Day18>(),
+    19 => Solution::of::<day19::Day19>(),
+    20 => Solution::of::<day20::Day20>(),
+    21 => Solution::of::<day21::Day21>(),
+    22 => Solution::of::<day22::Day22>(),
+    23 => Solution::of::<day23::Day23>(),
+    24 => Solution::of::<day24::Day24>(),
+    25 => Solution::of::<day25::Day25>(),
+    d => panic!("Provided unsupported day {}", d),
+  }
+}
+
 pub trait Day {
   type Input;
 
   fn parse(input_string: &str) -> IResult<&str, Self::Input>;
 
+  /// What both parts work on. Days that have nothing to share between the parts
+  /// use the input itself.
+  type Prepared;
+
+  /// The stage between parsing and the two parts, for work that both parts need.
+  /// It's timed separately, and can reject input that parsed but can't be solved.
+  fn prepare(input: Self::Input) -> Result<Self::Prepared, MyErr>;
+
   type Output1: Display;
 
-  fn part_1(input: &Self::Input) -> Self::Output1;
+  fn part_1(input: &Self::Prepared) -> Self::Output1;
 
   type Output2: Display;
 
-  fn part_2(input: &Self::Input) -> Self::Output2;
+  fn part_2(input: &Self::Prepared) -> Self::Output2;
 
   fn parse_file(fp: &str) -> Result<Self::Input, MyErr> {
     let input_string = read_to_string(fp)?;
@@ -49,16 +136,50 @@ pub trait Day {
     Ok(input)
   }
 
+  /// Solves both parts without printing anything, and returns the answers as they
+  /// would be printed.
+  fn solve(fp: &str) -> Result<(String, String), MyErr> {
+    set_stage(Stage::Parse);
+    let input = Self::parse_file(fp)?;
+    set_stage(Stage::Prepare);
+    let input = Self::prepare(input)?;
+    set_stage(Stage::Part(1));
+    let answer1 = Self::part_1(&input).to_string();
+    set_stage(Stage::Part(2));
+    let answer2 = Self::part_2(&input).to_string();
+    Ok((answer1, answer2))
+  }
+
+  /// Solves a single part of an input that is already in memory, and returns the
+  /// answer as it would be printed. Any part other than 1 is part 2.
+  fn solve_part(input_string: &str, part: usize) -> Result<String, MyErr> {
+    set_stage(Stage::Parse);
+    let (_, input) = Self::parse(input_string)?;
+    set_stage(Stage::Prepare);
+    let input = Self::prepare(input)?;
+    set_stage(Stage::Part(part));
+    if part == 1 {
+      Ok(Self::part_1(&input).to_string())
+    } else {
+      Ok(Self::part_2(&input).to_string())
+    }
+  }
+
   fn run_day(fp: &str) {
+    set_stage(Stage::Parse);
     match Self::parse_file(fp) {
-      Err(e) => println!("{:?}", e),
+      Err(e) => println!("{}", e),
       Ok(input) => {
-        let before1 = Instant::now();
-        println!("Part 1: {}", Self::part_1(&input));
-        println!("Part 1 took {}s", before1.elapsed().as_secs_f32());
-        let before2 = Instant::now();
-        println!("Part 2: {}", Self::part_2(&input));
-        println!("Part 2 took {}s", before2.elapsed().as_secs_f32());
+        set_stage(Stage::Prepare);
+        let before_prepare = Instant::now();
+        match Self::prepare(input) {
+          Err(e) => println!("{}", e),
+          Ok(input) => {
+            println!("Preparation took {}s", before_prepare.elapsed().as_secs_f32());
+            run_part(1, || Self::part_1(&input));
+            run_part(2, || Self::part_2(&input));
+          }
+        }
       }
     }
   }
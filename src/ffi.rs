@@ -0,0 +1,96 @@
+//! A C interface to the solutions, so they can be called from other languages.
+//! `include/aoc_template.h` is generated from this file and `plugin.rs` by
+//! build.rs, and the doc comments below end up in it.
+
+use crate::days::solution;
+use crate::YEAR;
+use std::ffi::{c_char, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// What `aoc_solve` returns.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+  Ok = 0,
+  /// Only the year of this crate is solved.
+  UnknownYear = 1,
+  /// Days go from 1 to 25.
+  UnknownDay = 2,
+  /// Parts are 1 or 2.
+  UnknownPart = 3,
+  /// The input or answer pointer is null, or the input is not UTF-8.
+  InvalidInput = 4,
+  /// The solution could not parse the input.
+  ParseError = 5,
+  /// The solution panicked, like days that aren't implemented yet.
+  Panicked = 6,
+  /// The answer has a NUL byte in it, so it can't be a C string.
+  InvalidAnswer = 7,
+}
+
+/// Solves one part of a day for the input of `input_len` bytes at `input_ptr`.
+/// On success, `*answer` points to the answer as a NUL-terminated string, which
+/// the caller owns and has to give back to `aoc_free_answer`. Otherwise
+/// `*answer` is set to null.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `input_len` readable bytes, and `answer` must
+/// point to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+  year: u32,
+  day: u32,
+  part: u32,
+  input_ptr: *const u8,
+  input_len: usize,
+  answer: *mut *mut c_char,
+) -> AocStatus {
+  if answer.is_null() {
+    return AocStatus::InvalidInput;
+  }
+  *answer = ptr::null_mut();
+  if year as usize != YEAR {
+    return AocStatus::UnknownYear;
+  }
+  if !(1..=25).contains(&day) {
+    return AocStatus::UnknownDay;
+  }
+  if part != 1 && part != 2 {
+    return AocStatus::UnknownPart;
+  }
+  if input_ptr.is_null() {
+    return AocStatus::InvalidInput;
+  }
+  let Ok(input) = std::str::from_utf8(slice::from_raw_parts(input_ptr, input_len)) else {
+    return AocStatus::InvalidInput;
+  };
+  // Unwinding into C is undefined behaviour, so panics have to stop here
+  let solve_part = solution(day as usize).solve_part;
+  match catch_unwind(AssertUnwindSafe(|| solve_part(input, part as usize))) {
+    Err(_) => AocStatus::Panicked,
+    Ok(Err(_)) => AocStatus::ParseError,
+    Ok(Ok(a)) => match CString::new(a) {
+      Ok(a) => {
+        *answer = a.into_raw();
+        AocStatus::Ok
+      }
+      Err(_) => AocStatus::InvalidAnswer,
+    },
+  }
+}
+
+/// Frees an answer returned by `aoc_solve`. Does nothing for null.
+///
+/// # Safety
+///
+/// `answer` must be null or a pointer from `aoc_solve` that hasn't been freed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_answer(answer: *mut c_char) {
+  if !answer.is_null() {
+    drop(CString::from_raw(answer));
+  }
+}
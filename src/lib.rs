@@ -0,0 +1,16 @@
+//! Solutions to this year's Advent of Code, and the utilities they share. The
+//! `aoc-template` binary is a command line interface around this library.
+
+pub mod days;
+pub mod ffi;
+pub mod logger;
+mod parser;
+pub mod plugin;
+pub mod runner;
+pub mod script;
+pub mod utils;
+
+pub use days::{Day, Solution};
+pub use parser::MyErr;
+
+pub const YEAR: usize = 2023;
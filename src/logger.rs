@@ -0,0 +1,92 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+// The stage of a day that is currently running, used to tag log lines.
+#[derive(Debug, Clone, Copy)]
+pub enum Stage {
+  Parse,
+  Prepare,
+  Part(usize),
+}
+
+impl fmt::Display for Stage {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Stage::Parse => write!(f, "parse"),
+      Stage::Prepare => write!(f, "prepare"),
+      Stage::Part(n) => write!(f, "part {}", n),
+    }
+  }
+}
+
+static CONTEXT: Mutex<(Option<usize>, Option<Stage>)> = Mutex::new((None, None));
+
+pub fn set_day(day: usize) {
+  *CONTEXT.lock().unwrap() = (Some(day), None);
+}
+
+pub fn set_stage(stage: Stage) {
+  CONTEXT.lock().unwrap().1 = Some(stage);
+}
+
+// Writes log lines to stderr or to a log file, so they never end up between the
+// answers on stdout.
+struct Logger {
+  out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Log for Logger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    // Only our own logs, not those of dependencies like reqwest
+    metadata.level() <= log::max_level() && metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+    let tag = match *CONTEXT.lock().unwrap() {
+      (Some(day), Some(stage)) => format!("day {:02} {}", day, stage),
+      (Some(day), None) => format!("day {:02}", day),
+      _ => "runner".to_owned(),
+    };
+    let mut out = self.out.lock().unwrap();
+    // There is nowhere left to report a failing log write to
+    let _ = writeln!(out, "[{} {}] {}", record.level(), tag, record.args());
+  }
+
+  fn flush(&self) {
+    let _ = self.out.lock().unwrap().flush();
+  }
+}
+
+// Sets up logging for the whole run. Without -v only warnings and errors are
+// logged, -v adds info, -vv adds debug and -vvv adds trace logs. When a log file
+// is given, it is overwritten with this run's logs instead of writing to stderr.
+// Worker processes append to the log file of the process that started them.
+pub fn init(verbosity: u8, log_file: Option<&str>, is_worker: bool) -> io::Result<()> {
+  let out: Box<dyn Write + Send> = match log_file {
+    Some(fp) => {
+      if !is_worker {
+        File::create(fp)?;
+      }
+      Box::new(OpenOptions::new().append(true).create(true).open(fp)?)
+    }
+    None => Box::new(io::stderr()),
+  };
+  let level = match verbosity {
+    0 => LevelFilter::Warn,
+    1 => LevelFilter::Info,
+    2 => LevelFilter::Debug,
+    _ => LevelFilter::Trace,
+  };
+  log::set_boxed_logger(Box::new(Logger {
+    out: Mutex::new(out),
+  }))
+  .expect("Logger was initialised twice");
+  log::set_max_level(level);
+  Ok(())
+}
@@ -1,14 +1,9 @@
-mod parser;
+use aoc_template::runner::{input_path, plugin_paths, run_worker, Task, Workers};
+use aoc_template::{logger, utils, YEAR};
 use chrono::prelude::*;
 use clap::{Parser, Subcommand, Args};
-use days::*;
-use std::fs;
-use std::time::Instant;
-
-mod days;
-mod utils;
-
-const YEAR: usize = 2023;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
 
 #[derive(Parser)]
 #[command(
@@ -20,6 +15,10 @@ const YEAR: usize = 2023;
 struct Cli {
     #[command(subcommand)]
     command: Command,
+    #[arg(short, long, action = clap::ArgAction::Count, global = true, help = "Log more details to stderr. Repeat for even more.")]
+    verbose: u8,
+    #[arg(long, global = true, help = "Write the logs of this run to a file instead of stderr.")]
+    log_file: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -27,7 +26,20 @@ enum Command {
   #[command(about = "Execute one or multiple days. Runs today's puzzle by default.")]
   Run {
     #[command(flatten)]
-    opts : DayOrAll
+    opts : DayOrAll,
+    #[command(flatten)]
+    run_opts : RunOpts,
+    #[arg(long, conflicts_with_all = ["all", "plugins"], help = "Solve the day with the part1 and part2 functions of this Rhai script, and compare the answers to the built-in solution.")]
+    script : Option<String>,
+  },
+  #[command(about = "Solve one or multiple days several times, and report answers that change between runs.")]
+  Check {
+    #[command(flatten)]
+    opts : DayOrAll,
+    #[command(flatten)]
+    run_opts : RunOpts,
+    #[arg(long, default_value_t = 5, help = "How many times to solve each day.")]
+    runs : usize,
   },
   #[command(about = "Download an input file. By default it will download today's input.")]
   GetInput {
@@ -45,12 +57,44 @@ struct DayOrAll {
   all : bool,
 }
 
+#[derive(Args)]
+struct RunOpts {
+  #[arg(long, default_value_t = 256, help = "Stack size in MiB of the thread that runs a day.")]
+  stack_size : usize,
+  #[arg(long, help = "Hash all maps and sets with a fixed seed, so they iterate in the same order on every run.")]
+  deterministic : bool,
+  #[arg(long, help = "Load days from the shared libraries in this directory. They replace the built-in days they solve.")]
+  plugins : Option<String>,
+  // Set for the child process that runs a single day, see `Workers::run_day`
+  #[arg(long, hide = true)]
+  worker : bool,
+  // The library in --plugins that solves the worker's day, so the worker doesn't
+  // have to load all of them again
+  #[arg(long, hide = true)]
+  plugin : Option<String>,
+}
+
 fn main() {
   let cli = Cli::parse();
+  let is_worker = match &cli.command {
+    Command::Run{run_opts, ..} | Command::Check{run_opts, ..} => run_opts.worker,
+    Command::GetInput{..} => false,
+  };
+  logger::init(cli.verbose, cli.log_file.as_deref(), is_worker).expect("Could not create log file");
+  // Worker processes log the same way as the process that started them
+  let mut log_args = vec!["-v".to_owned(); cli.verbose as usize];
+  if let Some(fp) = &cli.log_file {
+    log_args.extend(["--log-file".to_owned(), fp.clone()]);
+  }
   match cli.command {
-    Command::Run{opts} => run(opts),
+    Command::Run{opts, run_opts, script} => {
+      let task = script.map_or(Task::Run, Task::Script);
+      run(opts, task, &run_opts, &log_args)
+    }
+    Command::Check{opts, run_opts, runs} => run(opts, Task::Check(runs), &run_opts, &log_args),
     Command::GetInput{opts} => get_input(opts),
   }
+  log::logger().flush();
 }
 
 fn get_input(opts : DayOrAll) {
@@ -69,17 +113,37 @@ fn get_input(opts : DayOrAll) {
   }
 }
 
-fn run(opts : DayOrAll) {
-  if opts.all {
-    run_all_days()
+fn run(opts : DayOrAll, task: Task, run_opts: &RunOpts, log_args: &[String]) {
+  if run_opts.worker {
+    utils::set_deterministic(run_opts.deterministic);
+    let day = day_range_check(opts.day.expect("Workers need a day parameter"));
+    let plugin = run_opts.plugin.as_ref().map(PathBuf::from);
+    let succeeded = run_worker(day, task, run_opts.stack_size, plugin);
+    log::logger().flush();
+    if !succeeded {
+      process::exit(1);
+    }
   } else {
-    match opts.day {
-      Some(day) => run_day(day_range_check(day)),
-      None => {
-        println!("No day parameter specified, attempting to run today");
-        let now_day = get_today();
-        println!("Running day {}", now_day);
-        run_day(now_day);
+    let workers = Workers {
+      exe: env::current_exe().expect("Could not find the path of this executable"),
+      stack_size: run_opts.stack_size,
+      deterministic: run_opts.deterministic,
+      log_args: log_args.to_vec(),
+      plugins: plugin_paths(run_opts.plugins.as_deref().map(Path::new)),
+    };
+    if opts.all {
+      workers.run_all_days(&task);
+    } else {
+      match opts.day {
+        Some(day) => {
+          workers.run_day(day_range_check(day), &task);
+        }
+        None => {
+          println!("No day parameter specified, attempting to run today");
+          let now_day = get_today();
+          println!("Running day {}", now_day);
+          workers.run_day(now_day, &task);
+        }
       }
     }
   }
@@ -103,44 +167,6 @@ fn day_range_check(day: usize) -> usize {
   }
 }
 
-fn run_all_days() {
-  (1..=25).map(run_day).collect()
-}
-// Panics if you provide a value outside the range of 1 to 25
-fn run_day(day: usize) {
-  println!("======== DAY {} ========", day);
-  // I'd like to do this with a macro, but I'm not sure how to do it.
-  let input_fp = &format!("inputs/day{:02}.txt", day);
-  match day {
-    1 => day01::Day01::run_day(input_fp),
-    2 => day02::Day02::run_day(input_fp),
-    3 => day03::Day03::run_day(input_fp),
-    4 => day04::Day04::run_day(input_fp),
-    5 => day05::Day05::run_day(input_fp),
-    6 => day06::Day06::run_day(input_fp),
-    7 => day07::Day07::run_day(input_fp),
-    8 => day08::Day08::run_day(input_fp),
-    9 => day09::Day09::run_day(input_fp),
-    10 => day10::Day10::run_day(input_fp),
-    11 => day11::Day11::run_day(input_fp),
-    12 => day12::Day12::run_day(input_fp),
-    13 => day13::Day13::run_day(input_fp),
-    14 => day14::Day14::run_day(input_fp),
-    15 => day15::Day15::run_day(input_fp),
-    16 => day16::Day16::run_day(input_fp),
-    17 => day17::Day17::run_day(input_fp),
-    18 => day18::Day18::run_day(input_fp),
-    19 => day19::Day19::run_day(input_fp),
-    20 => day20::Day20::run_day(input_fp),
-    21 => day21::Day21::run_day(input_fp),
-    22 => day22::Day22::run_day(input_fp),
-    23 => day23::Day23::run_day(input_fp),
-    24 => day24::Day24::run_day(input_fp),
-    25 => day25::Day25::run_day(input_fp),
-    d => panic!("Provided unsupported day {}", d),
-  }
-}
-
 fn download_all_input() {
   (1..=25).map(download_input).collect()
 }
@@ -160,7 +186,7 @@ fn download_input(day: usize) {
     let mut text = response.text().unwrap();
     // Remove trailing newline
     text.pop();
-    let path = format!("inputs/day{:02}.txt", day);
+    let path = input_path(day);
     fs::write(&path, text).unwrap();
     println!("Successfully downloaded input to {}", &path);
   } else {
@@ -1,10 +1,23 @@
 use nom::error::Error;
 use nom::Err;
+use std::fmt;
 
 #[derive(Debug)]
 pub enum MyErr {
     FileError(std::io::Error),
     ParseError(Err<Error<String>>),
+    // Anything else, with a message that says what went wrong
+    Other(String),
+}
+
+impl fmt::Display for MyErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MyErr::FileError(e) => write!(f, "Could not read input file: {}", e),
+            MyErr::ParseError(e) => write!(f, "Could not parse input: {}", e),
+            MyErr::Other(e) => write!(f, "{}", e),
+        }
+    }
 }
 
 impl From<Err<Error<&str>>> for MyErr {
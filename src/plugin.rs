@@ -0,0 +1,218 @@
+//! Days loaded at runtime from shared libraries, for solutions that are written
+//! in another language, or that shouldn't be compiled into the binary. The types
+//! a plugin needs end up in `include/aoc_template.h`, together with the rest of
+//! the C interface.
+//!
+//! A plugin exports two functions: `aoc_plugin_abi_version`, which returns the
+//! `AOC_PLUGIN_ABI_VERSION` it was built against, and `aoc_plugin_descriptor`,
+//! which returns its `AocPluginDescriptor`. The version is checked before the
+//! descriptor is read, so plugins built for another layout are rejected without
+//! touching it.
+
+use crate::days::run_part;
+use crate::ffi::AocStatus;
+use crate::logger::{set_stage, Stage};
+use crate::utils::HashMap;
+use crate::{MyErr, YEAR};
+use libloading::{Library, Symbol};
+use log::{info, warn};
+use std::ffi::{c_char, CStr};
+use std::fmt;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+/// The version of `AocPluginDescriptor` and the functions in it. Changes
+/// whenever any of them change.
+pub const AOC_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Solves a part for the input of `input_len` bytes at `input`, with the same
+/// contract as `aoc_solve`: it returns one of the `AocStatus` values, and on
+/// success points `*answer` to a NUL-terminated string that is freed with the
+/// plugin's `free_answer`.
+pub type AocPartFn = unsafe extern "C" fn(input: *const u8, input_len: usize, answer: *mut *mut c_char) -> i32;
+
+/// Frees an answer returned by one of the part functions of the same plugin.
+pub type AocFreeFn = unsafe extern "C" fn(answer: *mut c_char);
+
+/// What a plugin solves, and how.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct AocPluginDescriptor {
+  pub year: u32,
+  pub day: u32,
+  pub part_1: AocPartFn,
+  pub part_2: AocPartFn,
+  pub free_answer: AocFreeFn,
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+  Load(libloading::Error),
+  AbiVersion(u32),
+  NullDescriptor,
+  WrongYear(u32),
+  InvalidDay(u32),
+  // A part returned a status other than AocStatus::Ok
+  Failed(i32),
+  NullAnswer,
+  NotUtf8,
+}
+
+impl fmt::Display for PluginError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PluginError::Load(e) => write!(f, "Could not load the library: {}", e),
+      PluginError::AbiVersion(v) => write!(
+        f,
+        "The plugin was built for ABI version {}, but this is version {}",
+        v, AOC_PLUGIN_ABI_VERSION
+      ),
+      PluginError::NullDescriptor => write!(f, "The plugin has no descriptor"),
+      PluginError::WrongYear(y) => write!(f, "The plugin is for {}, not {}", y, YEAR),
+      PluginError::InvalidDay(d) => write!(f, "The plugin is for day {}, which doesn't exist", d),
+      PluginError::Failed(status) => write!(f, "The plugin failed with status {}", status),
+      PluginError::NullAnswer => write!(f, "The plugin succeeded but gave no answer"),
+      PluginError::NotUtf8 => write!(f, "The answer of the plugin is not UTF-8"),
+    }
+  }
+}
+
+impl From<libloading::Error> for PluginError {
+  fn from(e: libloading::Error) -> PluginError {
+    PluginError::Load(e)
+  }
+}
+
+impl From<PluginError> for MyErr {
+  fn from(e: PluginError) -> MyErr {
+    MyErr::Other(format!("Plugin error: {}", e))
+  }
+}
+
+pub struct Plugin {
+  path: PathBuf,
+  descriptor: AocPluginDescriptor,
+  // The functions in the descriptor can only be called while this is loaded
+  _library: Library,
+}
+
+impl Plugin {
+  pub fn load(path: &Path) -> Result<Plugin, PluginError> {
+    // Loading a library runs its initialisers, and calling into it runs its
+    // code. Neither can be checked, so plugins have to be trusted.
+    let library = unsafe { Library::new(path) }?;
+    let version = unsafe {
+      let abi_version: Symbol<unsafe extern "C" fn() -> u32> = library.get(b"aoc_plugin_abi_version\0")?;
+      abi_version()
+    };
+    if version != AOC_PLUGIN_ABI_VERSION {
+      return Err(PluginError::AbiVersion(version));
+    }
+    let descriptor = unsafe {
+      let descriptor: Symbol<unsafe extern "C" fn() -> *const AocPluginDescriptor> =
+        library.get(b"aoc_plugin_descriptor\0")?;
+      *descriptor().as_ref().ok_or(PluginError::NullDescriptor)?
+    };
+    if descriptor.year as usize != YEAR {
+      return Err(PluginError::WrongYear(descriptor.year));
+    }
+    if !(1..=25).contains(&descriptor.day) {
+      return Err(PluginError::InvalidDay(descriptor.day));
+    }
+    Ok(Plugin {
+      path: path.to_owned(),
+      descriptor,
+      _library: library,
+    })
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn day(&self) -> usize {
+    self.descriptor.day as usize
+  }
+
+  // Any part other than 1 is part 2, like Day::solve_part
+  pub fn solve_part(&self, input: &str, part: usize) -> Result<String, PluginError> {
+    let solve = if part == 1 {
+      self.descriptor.part_1
+    } else {
+      self.descriptor.part_2
+    };
+    let mut answer = ptr::null_mut();
+    let status = unsafe { solve(input.as_ptr(), input.len(), &mut answer) };
+    if status != AocStatus::Ok as i32 {
+      return Err(PluginError::Failed(status));
+    }
+    if answer.is_null() {
+      return Err(PluginError::NullAnswer);
+    }
+    let res = unsafe { CStr::from_ptr(answer) }
+      .to_str()
+      .map(str::to_owned)
+      .map_err(|_| PluginError::NotUtf8);
+    unsafe { (self.descriptor.free_answer)(answer) };
+    res
+  }
+
+  // Like Day::solve
+  pub fn solve(&self, fp: &str) -> Result<(String, String), MyErr> {
+    let input = read_to_string(fp)?;
+    Ok((self.solve_part(&input, 1)?, self.solve_part(&input, 2)?))
+  }
+
+  // Like Day::run_day. A plugin doesn't have separate parsing and preparation,
+  // so only the parts are timed.
+  pub fn run_day(&self, fp: &str) {
+    set_stage(Stage::Parse);
+    match read_to_string(fp) {
+      Err(e) => println!("{}", MyErr::from(e)),
+      Ok(input) => {
+        for part in 1..=2 {
+          run_part(part, || {
+            self
+              .solve_part(&input, part)
+              .unwrap_or_else(|e| e.to_string())
+          });
+        }
+      }
+    }
+  }
+}
+
+// Loads every shared library in the directory as a plugin, by the day it solves.
+// Libraries that aren't valid plugins are skipped with a warning, and so are
+// plugins for a day that already has one.
+pub fn load_plugins(dir: &Path) -> HashMap<usize, Plugin> {
+  let mut plugins: HashMap<usize, Plugin> = HashMap::default();
+  let mut paths: Vec<PathBuf> = match read_dir(dir) {
+    Ok(entries) => entries.filter_map(|e| Some(e.ok()?.path())).collect(),
+    Err(e) => {
+      warn!("Could not read plugin directory {}: {}", dir.display(), e);
+      return plugins;
+    }
+  };
+  paths.retain(|p| p.extension().is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION));
+  paths.sort();
+  for path in paths {
+    match Plugin::load(&path) {
+      Err(e) => warn!("Skipping plugin {}: {}", path.display(), e),
+      Ok(plugin) => match plugins.get(&plugin.day()) {
+        Some(other) => warn!(
+          "Skipping plugin {}: {} already solves day {}",
+          path.display(),
+          other.path().display(),
+          plugin.day()
+        ),
+        None => {
+          info!("Loaded plugin {} for day {}", path.display(), plugin.day());
+          plugins.insert(plugin.day(), plugin);
+        }
+      },
+    }
+  }
+  plugins
+}
@@ -0,0 +1,206 @@
+//! Running days the way the `aoc-template` binary does: every day in a worker
+//! process of its own, on a thread with a large stack, with its plugin if it has
+//! one. The binary only turns its command line into calls to this module.
+
+use crate::days::solution;
+use crate::plugin::{load_plugins, Plugin};
+use crate::script::Script;
+use crate::utils::HashMap;
+use crate::{logger, MyErr};
+use std::path::{Path, PathBuf};
+use std::{process, thread};
+
+/// What to do with each day that is run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Task {
+  Run,
+  /// Solve the day this many times, and report answers that change between runs.
+  Check(usize),
+  /// Run the day with the script at this path.
+  Script(String),
+}
+
+/// How a worker process that ran a day ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+  Succeeded,
+  /// The day panicked, or the worker exited with an error some other way.
+  Failed,
+  /// The worker was killed, most likely because the day overflowed its stack.
+  Crashed,
+}
+
+/// How to start the worker processes that run the days.
+#[derive(Debug, Clone)]
+pub struct Workers {
+  /// The executable to start, which takes the arguments of `aoc-template`.
+  pub exe: PathBuf,
+  /// Stack size in MiB of the thread a worker runs its day on.
+  pub stack_size: usize,
+  pub deterministic: bool,
+  /// Passed on to every worker, so that they log the same way as this process.
+  pub log_args: Vec<String>,
+  /// The library of the plugin for every day that has one.
+  pub plugins: HashMap<usize, PathBuf>,
+}
+
+impl Workers {
+  /// Runs a day in a worker process. A stack overflow aborts the whole process,
+  /// so this way it only fails that day, and not the rest of the run.
+  pub fn run_day(&self, day: usize, task: &Task) -> Outcome {
+    let mut worker = process::Command::new(&self.exe);
+    match task {
+      Task::Run => worker.arg("run"),
+      Task::Check(runs) => worker.args(["check", "--runs", &runs.to_string()]),
+      Task::Script(path) => worker.args(["run", "--script", path]),
+    };
+    if self.deterministic {
+      worker.arg("--deterministic");
+    }
+    if let Some(path) = self.plugins.get(&day) {
+      worker.arg("--plugin").arg(path);
+    }
+    let status = worker
+      .args([&day.to_string(), "--worker"])
+      .args(["--stack-size", &self.stack_size.to_string()])
+      .args(&self.log_args)
+      .status()
+      .expect("Could not start a process to run the day in");
+    match status.code() {
+      Some(0) => Outcome::Succeeded,
+      Some(_) => {
+        println!("Day {} failed", day);
+        Outcome::Failed
+      }
+      None => {
+        println!(
+          "Day {} crashed, most likely because it overflowed its stack. Try a larger --stack-size.",
+          day
+        );
+        Outcome::Crashed
+      }
+    }
+  }
+
+  /// Runs every day, each in a worker process of its own.
+  pub fn run_all_days(&self, task: &Task) -> Vec<Outcome> {
+    (1..=25).map(|day| self.run_day(day, task)).collect()
+  }
+}
+
+/// The input file of a day.
+pub fn input_path(day: usize) -> String {
+  format!("inputs/day{:02}.txt", day)
+}
+
+/// The library of the plugin for every day that has one. They're loaded once to
+/// find out which day they solve, and to warn about the ones that are skipped.
+pub fn plugin_paths(plugin_dir: Option<&Path>) -> HashMap<usize, PathBuf> {
+  plugin_dir
+    .map(load_plugins)
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(day, plugin)| (day, plugin.path().to_owned()))
+    .collect()
+}
+
+/// Runs `f` on a thread with a stack of `stack_size` MiB, since some solutions
+/// recurse too deeply for the main thread's stack. Returns false if it panicked.
+pub fn on_big_stack<F: FnOnce() + Send + 'static>(name: String, stack_size: usize, f: F) -> bool {
+  thread::Builder::new()
+    .name(name)
+    .stack_size(stack_size * 1024 * 1024)
+    .spawn(f)
+    .expect("Could not start a thread to run the day on")
+    .join()
+    .is_ok()
+}
+
+/// What a worker process does: the task for a single day, with the plugin the
+/// parent process found for it, on a thread with a stack of `stack_size` MiB.
+/// Returns false if the day panicked.
+pub fn run_worker(day: usize, task: Task, stack_size: usize, plugin: Option<PathBuf>) -> bool {
+  on_big_stack(format!("day {}", day), stack_size, move || match task {
+    Task::Run => run_day(day, plugin.as_deref()),
+    Task::Check(runs) => check_day(day, runs, plugin.as_deref()),
+    Task::Script(path) => run_script(day, Path::new(&path)),
+  })
+}
+
+// Loads the plugin that the parent process found for a worker's day
+fn load_plugin(path: Option<&Path>) -> Option<Plugin> {
+  let path = path?;
+  match Plugin::load(path) {
+    Ok(plugin) => Some(plugin),
+    Err(e) => {
+      log::warn!("Skipping plugin {}: {}", path.display(), e);
+      None
+    }
+  }
+}
+
+/// Solves a day and prints the answers and how long every stage took, with the
+/// plugin at `plugin` instead of the built-in day if there is one.
+pub fn run_day(day: usize, plugin: Option<&Path>) {
+  println!("======== DAY {} ========", day);
+  logger::set_day(day);
+  match load_plugin(plugin) {
+    Some(plugin) => plugin.run_day(&input_path(day)),
+    None => (solution(day).run)(&input_path(day)),
+  }
+}
+
+/// Solves a day with the script at `path`, and compares its answers to the
+/// built-in day.
+pub fn run_script(day: usize, path: &Path) {
+  println!("======== DAY {} ========", day);
+  logger::set_day(day);
+  match Script::load(path) {
+    Ok(script) => script.run_day(day, &input_path(day)),
+    Err(e) => println!("{}", e),
+  }
+}
+
+/// The answers to both parts of a day.
+pub type Answers = (String, String);
+
+/// Calls `solve` `runs` times, and returns every distinct pair of answers with
+/// how many runs gave it, in the order they first came up. Stops at the first
+/// error.
+pub fn check_answers<F>(runs: usize, mut solve: F) -> Result<Vec<(Answers, usize)>, MyErr>
+where
+  F: FnMut() -> Result<Answers, MyErr>,
+{
+  let mut answers: Vec<(Answers, usize)> = vec![];
+  for _ in 0..runs {
+    let a = solve()?;
+    match answers.iter_mut().find(|(a2, _)| *a2 == a) {
+      Some((_, count)) => *count += 1,
+      None => answers.push((a, 1)),
+    }
+  }
+  Ok(answers)
+}
+
+/// Solves a day several times, and reports it when the answers differ between
+/// runs. That usually means they depend on the iteration order of a map or set.
+pub fn check_day(day: usize, runs: usize, plugin: Option<&Path>) {
+  println!("======== DAY {} ========", day);
+  logger::set_day(day);
+  let plugin = load_plugin(plugin);
+  let fp = input_path(day);
+  let answers = check_answers(runs, || match &plugin {
+    Some(plugin) => plugin.solve(&fp),
+    None => (solution(day).solve)(&fp),
+  });
+  match answers {
+    Err(e) => println!("{}", e),
+    Ok(answers) if answers.len() == 1 => println!("Answers were the same in all {} runs", runs),
+    Ok(answers) => {
+      println!("Answers changed between runs!");
+      for ((answer1, answer2), count) in answers {
+        println!("{} runs gave part 1: {}, part 2: {}", count, answer1, answer2);
+      }
+    }
+  }
+}
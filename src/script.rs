@@ -0,0 +1,376 @@
+//! Days solved by [Rhai](https://rhai.rs) scripts, for quick experiments that
+//! shouldn't need a recompile. A script defines `part1(input)` and
+//! `part2(input)`, which get the input as a string and return the answer. It can
+//! use `Pos`, a `Grid` of characters and the searches from `utils`:
+//!
+//! ```
+//! use aoc_template::script::Script;
+//!
+//! let script = Script::new(r#"
+//!   fn part1(input) {
+//!     let grid = grid(input);
+//!     let start = grid.find('S');
+//!     let res = bfs(start, |p| neighbours_4(grid, p).filter(|n| grid[n] != '#'));
+//!     res.cost(grid.find('E'))
+//!   }
+//!   fn part2(input) { grid(input).width }
+//! "#).unwrap();
+//! let maze = "S.#\n#.#\n..E";
+//! assert_eq!(script.solve_part(maze, 1).unwrap(), "4");
+//! assert_eq!(script.solve_part(maze, 2).unwrap(), "3");
+//! ```
+//!
+//! Positions are made with `pos(x, y)`, and can be added, subtracted and
+//! compared. A grid is made from text with `grid(text)`, and indexed with
+//! positions. `bfs(start, successors)` and `dijkstra(start, successors)` search
+//! over positions, optionally with an `is_goal` function as a third argument.
+//! The successors for `dijkstra` are `[position, cost]` pairs. Both return a
+//! result with `cost(pos)`, `path_to(pos)`, `goal`, `goal_cost` and `reached()`.
+//!
+//! Rhai locks a variable while a chain of method calls on it runs, so a closure
+//! in `grid.neighbours_4(p).filter(|n| grid[n] != '#')` can't read the grid. Call
+//! the first method as a function instead, like in the example above.
+
+use crate::days::{run_part, solution};
+use crate::logger::{set_stage, Stage};
+use crate::utils::{Grid, Pos, Search, SearchResult};
+use crate::MyErr;
+use log::debug;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FnPtr, NativeCallContext, ParseError, Scope, AST, INT};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum ScriptError {
+  Io(std::io::Error),
+  Compile(ParseError),
+  Eval(Box<EvalAltResult>),
+}
+
+impl fmt::Display for ScriptError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ScriptError::Io(e) => write!(f, "Could not read the script: {}", e),
+      ScriptError::Compile(e) => write!(f, "Could not compile the script: {}", e),
+      ScriptError::Eval(e) => write!(f, "Script error: {}", e),
+    }
+  }
+}
+
+impl From<std::io::Error> for ScriptError {
+  fn from(e: std::io::Error) -> ScriptError {
+    ScriptError::Io(e)
+  }
+}
+
+impl From<ParseError> for ScriptError {
+  fn from(e: ParseError) -> ScriptError {
+    ScriptError::Compile(e)
+  }
+}
+
+impl From<Box<EvalAltResult>> for ScriptError {
+  fn from(e: Box<EvalAltResult>) -> ScriptError {
+    ScriptError::Eval(e)
+  }
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+pub struct Script {
+  engine: Engine,
+  ast: AST,
+}
+
+impl Script {
+  pub fn new(source: &str) -> Result<Script, ScriptError> {
+    let engine = engine();
+    let ast = engine.compile(source)?;
+    Ok(Script { engine, ast })
+  }
+
+  pub fn load(path: &Path) -> Result<Script, ScriptError> {
+    Script::new(&read_to_string(path)?)
+  }
+
+  // Any part other than 1 is part 2, like Day::solve_part
+  pub fn solve_part(&self, input: &str, part: usize) -> Result<String, ScriptError> {
+    let name = if part == 1 { "part1" } else { "part2" };
+    let answer: Dynamic = self
+      .engine
+      .call_fn(&mut Scope::new(), &self.ast, name, (input.to_owned(),))?;
+    Ok(answer.to_string())
+  }
+
+  // Like Day::run_day, and then compares the answers to those of the built-in
+  // solution of the day. A script doesn't have separate parsing and
+  // preparation, so only the parts are timed.
+  pub fn run_day(&self, day: usize, fp: &str) {
+    set_stage(Stage::Parse);
+    let input = match read_to_string(fp) {
+      Ok(input) => input,
+      Err(e) => return println!("{}", MyErr::from(e)),
+    };
+    let mut answers = vec![];
+    for part in 1..=2 {
+      run_part(part, || match self.solve_part(&input, part) {
+        Ok(answer) => {
+          answers.push(Some(answer.clone()));
+          answer
+        }
+        Err(e) => {
+          answers.push(None);
+          e.to_string()
+        }
+      });
+    }
+    verify(day, fp, &answers);
+  }
+}
+
+// Prints whether the answers of a script are the same as those of the built-in
+// solution. Parts the script failed on are skipped. Every day has a built-in
+// solution, so if it panics, that's a bug in it, and fails the day like it would
+// without a script.
+fn verify(day: usize, fp: &str, answers: &[Option<String>]) {
+  match (solution(day).solve)(fp) {
+    Err(e) => println!("Could not verify the answers: {}", e),
+    Ok((answer1, answer2)) => {
+      for (part, (script, built_in)) in answers.iter().zip([answer1, answer2]).enumerate() {
+        match script {
+          Some(a) if *a == built_in => println!("Part {} matches the built-in solution", part + 1),
+          Some(_) => println!(
+            "Part {} differs from the built-in solution, which gives {}",
+            part + 1,
+            built_in
+          ),
+          None => {}
+        }
+      }
+    }
+  }
+}
+
+fn engine() -> Engine {
+  let mut engine = Engine::new();
+  // The default limits are low in debug builds, and scripts are trusted anyway
+  engine.set_max_expr_depths(0, 0);
+  engine.on_debug(|s, _, _| debug!("{}", s));
+  register_pos(&mut engine);
+  register_grid(&mut engine);
+  register_search(&mut engine);
+  engine
+}
+
+fn pos_or_unit(pos: Option<Pos>) -> Dynamic {
+  pos.map_or(Dynamic::UNIT, Dynamic::from)
+}
+
+fn positions(positions: impl Iterator<Item = Pos>) -> Array {
+  positions.map(Dynamic::from).collect()
+}
+
+// Rhai's ints are 64 bits, and positions only have 32
+fn coordinate(v: INT) -> ScriptResult<i32> {
+  i32::try_from(v).map_err(|_| format!("{} doesn't fit in a coordinate", v).into())
+}
+
+fn register_pos(engine: &mut Engine) {
+  engine
+    .register_type_with_name::<Pos>("Pos")
+    .register_fn("pos", |x: INT, y: INT| -> ScriptResult<Pos> {
+      Ok(Pos::new(coordinate(x)?, coordinate(y)?))
+    })
+    .register_get_set(
+      "x",
+      |p: &mut Pos| p.x() as INT,
+      |p: &mut Pos, x: INT| -> ScriptResult<()> {
+        p.0[0] = coordinate(x)?;
+        Ok(())
+      },
+    )
+    .register_get_set(
+      "y",
+      |p: &mut Pos| p.y() as INT,
+      |p: &mut Pos, y: INT| -> ScriptResult<()> {
+        p.0[1] = coordinate(y)?;
+        Ok(())
+      },
+    )
+    .register_fn("+", |a: Pos, b: Pos| a + b)
+    .register_fn("-", |a: Pos, b: Pos| a - b)
+    .register_fn("==", |a: Pos, b: Pos| a == b)
+    .register_fn("!=", |a: Pos, b: Pos| a != b)
+    .register_fn("manhattan", |a: Pos, b: Pos| a.manhattan(b) as INT)
+    .register_fn("to_string", |p: &mut Pos| p.to_string())
+    .register_fn("to_debug", |p: &mut Pos| format!("pos({}, {})", p.x(), p.y()));
+}
+
+fn register_grid(engine: &mut Engine) {
+  engine
+    .register_type_with_name::<Grid<char>>("Grid")
+    .register_fn("grid", |text: &str| -> ScriptResult<Grid<char>> {
+      let rows: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+      if rows.iter().any(|r| r.len() != rows[0].len()) {
+        return Err("All lines of a grid must have the same length".into());
+      }
+      Ok(Grid::from_rows(rows))
+    })
+    .register_get("width", |g: &mut Grid<char>| g.width() as INT)
+    .register_get("height", |g: &mut Grid<char>| g.height() as INT)
+    .register_fn("contains", |g: &mut Grid<char>, p: Pos| g.contains(p))
+    // Unlike indexing, get returns () outside of the grid
+    .register_fn("get", |g: &mut Grid<char>, p: Pos| {
+      g.get(p).map_or(Dynamic::UNIT, |c| Dynamic::from(*c))
+    })
+    .register_indexer_get(|g: &mut Grid<char>, p: Pos| -> ScriptResult<char> {
+      g.get(p)
+        .copied()
+        .ok_or_else(|| format!("{} is outside of the grid", p).into())
+    })
+    .register_indexer_set(|g: &mut Grid<char>, p: Pos, c: char| -> ScriptResult<()> {
+      *g.get_mut(p).ok_or_else(|| format!("{} is outside of the grid", p))? = c;
+      Ok(())
+    })
+    .register_fn("find", |g: &mut Grid<char>, c: char| pos_or_unit(g.find(|v| *v == c)))
+    .register_fn("positions", |g: &mut Grid<char>| positions(g.positions()))
+    .register_fn("neighbours_4", |g: &mut Grid<char>, p: Pos| positions(g.neighbours_4(p)))
+    .register_fn("neighbours_8", |g: &mut Grid<char>, p: Pos| positions(g.neighbours_8(p)))
+    .register_fn("to_string", |g: &mut Grid<char>| {
+      g.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+    });
+}
+
+// Calls functions of the script from inside a search. A search can't be stopped
+// halfway, so after the first error nothing is called anymore, and the search
+// runs out of nodes. The error is returned when the search is done.
+struct Callbacks<'a> {
+  context: &'a NativeCallContext<'a>,
+  error: RefCell<Option<Box<EvalAltResult>>>,
+}
+
+impl<'a> Callbacks<'a> {
+  fn new(context: &'a NativeCallContext<'a>) -> Self {
+    Callbacks {
+      context,
+      error: RefCell::new(None),
+    }
+  }
+
+  fn call(&self, f: &FnPtr, pos: Pos) -> Option<Dynamic> {
+    if self.error.borrow().is_some() {
+      return None;
+    }
+    self.check(f.call_within_context(self.context, (pos,)))
+  }
+
+  // Converts the successors a script returned, with `convert` for every one
+  fn successors<T>(&self, f: &FnPtr, pos: Pos, convert: fn(Dynamic) -> ScriptResult<T>) -> Vec<T> {
+    let Some(next) = self.call(f, pos) else {
+      return vec![];
+    };
+    let type_name = next.type_name();
+    let next = next
+      .try_cast::<Array>()
+      .ok_or_else(|| format!("Expected successors of type array, but got {}", type_name).into())
+      .and_then(|next| next.into_iter().map(convert).collect());
+    self.check(next).unwrap_or_default()
+  }
+
+  fn check<T>(&self, res: ScriptResult<T>) -> Option<T> {
+    res.map_err(|e| *self.error.borrow_mut() = Some(e)).ok()
+  }
+
+  fn is_goal(&self, is_goal: Option<&FnPtr>, pos: Pos) -> bool {
+    let Some(goal) = is_goal.and_then(|f| self.call(f, pos)) else {
+      return false;
+    };
+    let goal = goal
+      .as_bool()
+      .map_err(|t| format!("Expected is_goal to return a bool, but got {}", t).into());
+    self.check(goal).unwrap_or(false)
+  }
+
+  fn finish(self, res: SearchResult<Pos>) -> ScriptResult<SearchResult<Pos>> {
+    match self.error.into_inner() {
+      Some(e) => Err(e),
+      None => Ok(res),
+    }
+  }
+}
+
+fn to_pos(value: Dynamic) -> ScriptResult<Pos> {
+  let type_name = value.type_name();
+  value
+    .try_cast::<Pos>()
+    .ok_or_else(|| format!("Expected a successor of type Pos, but got {}", type_name).into())
+}
+
+// A [position, cost] pair
+fn to_step(value: Dynamic) -> ScriptResult<(Pos, usize)> {
+  let step = value
+    .try_cast::<Array>()
+    .filter(|a| a.len() == 2)
+    .ok_or("Expected a successor of the form [position, cost]")?;
+  let cost = step[1].as_int().map_err(|t| format!("Expected a cost of type int, but got {}", t))?;
+  let cost = usize::try_from(cost).map_err(|_| format!("Costs can't be negative, but got {}", cost))?;
+  Ok((to_pos(step[0].clone())?, cost))
+}
+
+fn bfs(context: NativeCallContext, start: Pos, successors: FnPtr, is_goal: Option<FnPtr>) -> ScriptResult<SearchResult<Pos>> {
+  let callbacks = Callbacks::new(&context);
+  let res = Search::with_paths().bfs(
+    start,
+    |p| callbacks.successors(&successors, *p, to_pos),
+    |p| callbacks.is_goal(is_goal.as_ref(), *p),
+  );
+  callbacks.finish(res)
+}
+
+fn dijkstra(
+  context: NativeCallContext,
+  start: Pos,
+  successors: FnPtr,
+  is_goal: Option<FnPtr>,
+) -> ScriptResult<SearchResult<Pos>> {
+  let callbacks = Callbacks::new(&context);
+  let res = Search::with_paths().dijkstra(
+    start,
+    |p| callbacks.successors(&successors, *p, to_step),
+    |p| callbacks.is_goal(is_goal.as_ref(), *p),
+  );
+  callbacks.finish(res)
+}
+
+fn register_search(engine: &mut Engine) {
+  engine
+    .register_type_with_name::<SearchResult<Pos>>("SearchResult")
+    .register_fn("bfs", |c: NativeCallContext, start: Pos, next: FnPtr| bfs(c, start, next, None))
+    .register_fn("bfs", |c: NativeCallContext, start: Pos, next: FnPtr, goal: FnPtr| {
+      bfs(c, start, next, Some(goal))
+    })
+    .register_fn("dijkstra", |c: NativeCallContext, start: Pos, next: FnPtr| {
+      dijkstra(c, start, next, None)
+    })
+    .register_fn("dijkstra", |c: NativeCallContext, start: Pos, next: FnPtr, goal: FnPtr| {
+      dijkstra(c, start, next, Some(goal))
+    })
+    .register_fn("cost", |r: &mut SearchResult<Pos>, p: Pos| {
+      r.cost(&p).map_or(Dynamic::UNIT, |c| Dynamic::from(c as INT))
+    })
+    .register_fn("path_to", |r: &mut SearchResult<Pos>, p: Pos| {
+      r.path_to(&p).map_or(Dynamic::UNIT, |path| positions(path.into_iter()).into())
+    })
+    .register_get("goal", |r: &mut SearchResult<Pos>| pos_or_unit(r.goal))
+    .register_get("goal_cost", |r: &mut SearchResult<Pos>| {
+      r.goal_cost().map_or(Dynamic::UNIT, |c| Dynamic::from(c as INT))
+    })
+    // Row by row, so scripts don't depend on the order of the map
+    .register_fn("reached", |r: &mut SearchResult<Pos>| {
+      let mut reached: Vec<Pos> = r.costs.keys().copied().collect();
+      reached.sort_by_key(|p| (p.y(), p.x()));
+      positions(reached.into_iter())
+    });
+}
@@ -0,0 +1,291 @@
+//! Sets of small numbers and of grid positions, stored as one bit each.
+//!
+//! ```
+//! use aoc_template::utils::{BitGrid, BitSet, Pos};
+//!
+//! let mut set = BitSet::new(100);
+//! assert!(set.insert(42));
+//! assert!(!set.insert(42));
+//! assert_eq!(set.iter().collect::<Vec<_>>(), vec![42]);
+//!
+//! let mut seen = BitGrid::new(10, 10);
+//! seen.insert(Pos::new(3, 4));
+//! assert!(seen.contains(Pos::new(3, 4)));
+//! assert!(!seen.contains(Pos::new(-1, 4)));
+//! ```
+
+use crate::utils::{Direction, Grid, Pos};
+
+/// A fixed size set of small numbers, stored as one bit per number. Cloning it
+/// allocates, but only a word per 64 numbers, so giving every branch of a search
+/// its own copy is still fairly cheap.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitSet {
+  words: Vec<u64>,
+  capacity: usize,
+}
+
+impl BitSet {
+  /// A set that can hold the numbers 0 up to `capacity`
+  pub fn new(capacity: usize) -> Self {
+    BitSet {
+      words: vec![0; capacity.div_ceil(64)],
+      capacity,
+    }
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  fn check(&self, i: usize) {
+    assert!(i < self.capacity, "{} is too large for a BitSet of {}", i, self.capacity);
+  }
+
+  /// Returns whether the number wasn't in the set yet, like HashSet::insert
+  pub fn insert(&mut self, i: usize) -> bool {
+    self.check(i);
+    let (word, bit) = (i / 64, 1 << (i % 64));
+    let is_new = self.words[word] & bit == 0;
+    self.words[word] |= bit;
+    is_new
+  }
+
+  /// Returns whether the number was in the set
+  pub fn remove(&mut self, i: usize) -> bool {
+    self.check(i);
+    let (word, bit) = (i / 64, 1 << (i % 64));
+    let was_set = self.words[word] & bit != 0;
+    self.words[word] &= !bit;
+    was_set
+  }
+
+  pub fn contains(&self, i: usize) -> bool {
+    i < self.capacity && self.words[i / 64] & (1 << (i % 64)) != 0
+  }
+
+  /// The number of numbers in the set
+  pub fn count(&self) -> usize {
+    self.words.iter().map(|w| w.count_ones() as usize).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.iter().all(|w| *w == 0)
+  }
+
+  pub fn clear(&mut self) {
+    self.words.iter_mut().for_each(|w| *w = 0);
+  }
+
+  fn check_same_capacity(&self, other: &BitSet) {
+    assert_eq!(self.capacity, other.capacity, "BitSets have different capacities");
+  }
+
+  pub fn union_with(&mut self, other: &BitSet) {
+    self.check_same_capacity(other);
+    self.words.iter_mut().zip(other.words.iter()).for_each(|(a, b)| *a |= b);
+  }
+
+  pub fn intersect_with(&mut self, other: &BitSet) {
+    self.check_same_capacity(other);
+    self.words.iter_mut().zip(other.words.iter()).for_each(|(a, b)| *a &= b);
+  }
+
+  pub fn union(&self, other: &BitSet) -> BitSet {
+    let mut res = self.clone();
+    res.union_with(other);
+    res
+  }
+
+  pub fn intersection(&self, other: &BitSet) -> BitSet {
+    let mut res = self.clone();
+    res.intersect_with(other);
+    res
+  }
+
+  /// The numbers in the set, from small to large
+  pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    self.words.iter().enumerate().flat_map(|(i, w)| {
+      let mut w = *w;
+      std::iter::from_fn(move || {
+        (w != 0).then(|| {
+          let bit = w.trailing_zeros() as usize;
+          w &= w - 1;
+          i * 64 + bit
+        })
+      })
+    })
+  }
+}
+
+/// Which directions the layers of a BitGrid stand for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirLayers {
+  /// The layers are only numbered, so there are none to insert a direction in
+  None,
+  /// A layer for every cardinal direction, in the order of CARDINALS
+  Cardinals,
+  /// A layer for every direction, in the order of ALL
+  All,
+}
+
+/// A set of positions in a grid, optionally with a few layers per position, like
+/// one for every direction a beam can pass a tile in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+  width: usize,
+  height: usize,
+  layers: usize,
+  dirs: DirLayers,
+  bits: BitSet,
+}
+
+impl BitGrid {
+  pub fn new(width: usize, height: usize) -> Self {
+    BitGrid::with_layers(width, height, 1)
+  }
+
+  pub fn with_layers(width: usize, height: usize, layers: usize) -> Self {
+    BitGrid {
+      width,
+      height,
+      layers,
+      dirs: DirLayers::None,
+      bits: BitSet::new(width * height * layers),
+    }
+  }
+
+  /// A layer for every direction of the layout, which the `_dir` methods use
+  pub fn with_dir_layers(width: usize, height: usize, dirs: DirLayers) -> Self {
+    let layers = match dirs {
+      DirLayers::None => 1,
+      DirLayers::Cardinals => Direction::CARDINALS.len(),
+      DirLayers::All => Direction::ALL.len(),
+    };
+    BitGrid {
+      dirs,
+      ..BitGrid::with_layers(width, height, layers)
+    }
+  }
+
+  /// An empty set the size of the grid
+  pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+    BitGrid::new(grid.width(), grid.height())
+  }
+
+  /// An empty set the size of the grid, with a layer for every direction
+  pub fn for_grid_with_directions<T>(grid: &Grid<T>) -> Self {
+    BitGrid::with_dir_layers(grid.width(), grid.height(), DirLayers::All)
+  }
+
+  /// An empty set the size of the grid, with a layer for every cardinal direction
+  /// only. Inserting a diagonal direction panics.
+  pub fn for_grid_with_cardinals<T>(grid: &Grid<T>) -> Self {
+    BitGrid::with_dir_layers(grid.width(), grid.height(), DirLayers::Cardinals)
+  }
+
+  fn dir_layer(&self, dir: Direction) -> usize {
+    match self.dirs {
+      DirLayers::None => panic!("{:?} has no layer in a BitGrid without direction layers", dir),
+      DirLayers::Cardinals => dir
+        .cardinal_index()
+        .unwrap_or_else(|| panic!("{:?} has no layer in a BitGrid of cardinals", dir)),
+      DirLayers::All => dir.index(),
+    }
+  }
+
+  fn index(&self, pos: Pos, layer: usize) -> Option<usize> {
+    let in_grid = pos.x() >= 0
+      && pos.y() >= 0
+      && (pos.x() as usize) < self.width
+      && (pos.y() as usize) < self.height
+      && layer < self.layers;
+    in_grid.then(|| (pos.y() as usize * self.width + pos.x() as usize) * self.layers + layer)
+  }
+
+  fn index_or_panic(&self, pos: Pos, layer: usize) -> usize {
+    self
+      .index(pos, layer)
+      .unwrap_or_else(|| panic!("{:?} in layer {} is outside of the BitGrid", pos, layer))
+  }
+
+  pub fn insert(&mut self, pos: Pos) -> bool {
+    self.insert_in(pos, 0)
+  }
+
+  pub fn insert_in(&mut self, pos: Pos, layer: usize) -> bool {
+    let i = self.index_or_panic(pos, layer);
+    self.bits.insert(i)
+  }
+
+  pub fn insert_dir(&mut self, pos: Pos, dir: Direction) -> bool {
+    self.insert_in(pos, self.dir_layer(dir))
+  }
+
+  pub fn remove(&mut self, pos: Pos) -> bool {
+    self.remove_in(pos, 0)
+  }
+
+  pub fn remove_in(&mut self, pos: Pos, layer: usize) -> bool {
+    let i = self.index_or_panic(pos, layer);
+    self.bits.remove(i)
+  }
+
+  /// Positions outside the grid are never in the set
+  pub fn contains(&self, pos: Pos) -> bool {
+    self.contains_in(pos, 0)
+  }
+
+  pub fn contains_in(&self, pos: Pos, layer: usize) -> bool {
+    self.index(pos, layer).is_some_and(|i| self.bits.contains(i))
+  }
+
+  pub fn contains_dir(&self, pos: Pos, dir: Direction) -> bool {
+    self.contains_in(pos, self.dir_layer(dir))
+  }
+
+  /// The number of bits that are set, over all layers
+  pub fn count(&self) -> usize {
+    self.bits.count()
+  }
+
+  /// The number of positions that are set in at least one layer
+  pub fn count_positions(&self) -> usize {
+    if self.layers == 1 {
+      return self.count();
+    }
+    let mut last = None;
+    self
+      .bits
+      .iter()
+      .filter(|i| {
+        let pos = i / self.layers;
+        let is_new = last != Some(pos);
+        last = Some(pos);
+        is_new
+      })
+      .count()
+  }
+
+  pub fn clear(&mut self) {
+    self.bits.clear();
+  }
+
+  fn check_same_shape(&self, other: &BitGrid) {
+    assert_eq!(
+      (self.width, self.height, self.layers, self.dirs),
+      (other.width, other.height, other.layers, other.dirs),
+      "BitGrids have different sizes or layers"
+    );
+  }
+
+  pub fn union_with(&mut self, other: &BitGrid) {
+    self.check_same_shape(other);
+    self.bits.union_with(&other.bits);
+  }
+
+  pub fn intersect_with(&mut self, other: &BitGrid) {
+    self.check_same_shape(other);
+    self.bits.intersect_with(&other.bits);
+  }
+}
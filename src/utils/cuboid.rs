@@ -0,0 +1,156 @@
+//! Boxes in 3D space, and a height map to find what they land on when stacked.
+//!
+//! ```
+//! use aoc_template::utils::{Cuboid, HeightMap, Point3};
+//!
+//! let floor = Cuboid::new(Point3::new(0, 0, 1), Point3::new(2, 0, 1));
+//! let beam = Cuboid::new(Point3::new(1, 0, 5), Point3::new(1, 2, 5));
+//! assert_eq!(beam.volume(), 3);
+//! assert!(floor.overlaps_xy(&beam));
+//!
+//! let mut stack = HeightMap::new();
+//! stack.fall(&floor, 'A', 1);
+//! let (landed, supports) = stack.fall(&beam, 'B', 1);
+//! assert_eq!(landed.bottom(), 2);
+//! assert_eq!(supports, vec!['A']);
+//! ```
+
+use crate::utils::{HashMap, Point3, Pos};
+
+/// An axis-aligned box of whole cubes, with both corners included, like a brick
+/// that goes from 1,0,1 to 1,2,1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+  pub min: Point3<i32>,
+  pub max: Point3<i32>,
+}
+
+impl Cuboid {
+  /// The box between two opposite corners, in any order
+  pub fn new(a: Point3<i32>, b: Point3<i32>) -> Self {
+    Cuboid {
+      min: a.min(b),
+      max: a.max(b),
+    }
+  }
+
+  pub fn bottom(&self) -> i32 {
+    self.min.z()
+  }
+
+  pub fn top(&self) -> i32 {
+    self.max.z()
+  }
+
+  /// The number of cubes along each axis
+  pub fn size(&self) -> Point3<i32> {
+    self.max - self.min + Point3::new(1, 1, 1)
+  }
+
+  pub fn volume(&self) -> i64 {
+    self.size().0.iter().map(|s| *s as i64).product()
+  }
+
+  pub fn contains(&self, p: Point3<i32>) -> bool {
+    p.within(self.min, self.max)
+  }
+
+  pub fn overlaps(&self, other: &Cuboid) -> bool {
+    (0..3).all(|i| self.min.0[i] <= other.max.0[i] && other.min.0[i] <= self.max.0[i])
+  }
+
+  pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+    self.overlaps(other).then(|| Cuboid {
+      min: self.min.max(other.min),
+      max: self.max.min(other.max),
+    })
+  }
+
+  /// Whether the boxes would hit each other when one moves straight up or down
+  pub fn overlaps_xy(&self, other: &Cuboid) -> bool {
+    (0..2).all(|i| self.min.0[i] <= other.max.0[i] && other.min.0[i] <= self.max.0[i])
+  }
+
+  /// The positions the box covers when looking down on it
+  pub fn footprint(&self) -> impl Iterator<Item = Pos> {
+    let (min, max) = (self.min, self.max);
+    (min.y()..=max.y()).flat_map(move |y| (min.x()..=max.x()).map(move |x| Pos::new(x, y)))
+  }
+
+  pub fn translate(&self, by: Point3<i32>) -> Cuboid {
+    Cuboid {
+      min: self.min + by,
+      max: self.max + by,
+    }
+  }
+
+  /// The same box moved up or down, so its bottom is at z
+  pub fn with_bottom_at(&self, z: i32) -> Cuboid {
+    self.translate(Point3::new(0, 0, z - self.bottom()))
+  }
+}
+
+/// The highest box on every position when looking down on a stack of boxes, to
+/// find what a box lands on without comparing it to all the others. Boxes have to
+/// be placed from the bottom up.
+#[derive(Debug, Clone)]
+pub struct HeightMap<K> {
+  tops: HashMap<Pos, (i32, K)>,
+}
+
+impl<K: Copy + Eq> HeightMap<K> {
+  pub fn new() -> Self {
+    HeightMap {
+      tops: HashMap::default(),
+    }
+  }
+
+  /// The top of the highest box on the position, if there is one
+  pub fn height_at(&self, pos: Pos) -> Option<i32> {
+    self.tops.get(&pos).map(|(z, _)| *z)
+  }
+
+  /// The highest top directly below the footprint of the box, and the keys of all
+  /// boxes that reach it. None if there is nothing below the box.
+  pub fn below(&self, cuboid: &Cuboid) -> Option<(i32, Vec<K>)> {
+    let mut res: Option<(i32, Vec<K>)> = None;
+    for (z, key) in cuboid.footprint().filter_map(|p| self.tops.get(&p)) {
+      match res.as_mut() {
+        Some((top, keys)) if *top == *z => {
+          if !keys.contains(key) {
+            keys.push(*key);
+          }
+        }
+        Some((top, _)) if *top > *z => {}
+        _ => res = Some((*z, vec![*key])),
+      }
+    }
+    res
+  }
+
+  /// Puts the box on top of whatever is on its footprint
+  pub fn place(&mut self, cuboid: &Cuboid, key: K) {
+    for p in cuboid.footprint() {
+      self.tops.insert(p, (cuboid.top(), key));
+    }
+  }
+
+  /// Lets the box fall until it lands on another box or on the ground at
+  /// `floor`, and places it there. Returns where it ended up and the keys of the
+  /// boxes it rests on.
+  pub fn fall(&mut self, cuboid: &Cuboid, key: K, floor: i32) -> (Cuboid, Vec<K>) {
+    let (bottom, supports) = match self.below(cuboid) {
+      Some((top, keys)) => (top + 1, keys),
+      None => (floor, vec![]),
+    };
+    let landed = cuboid.with_bottom_at(bottom);
+    self.place(&landed, key);
+    (landed, supports)
+  }
+}
+
+impl<K: Copy + Eq> Default for HeightMap<K> {
+  fn default() -> Self {
+    HeightMap::new()
+  }
+}
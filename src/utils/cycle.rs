@@ -0,0 +1,99 @@
+//! Skipping ahead in simulations that end up repeating themselves.
+//!
+//! ```
+//! use aoc_template::utils::{find_cycle, state_at};
+//!
+//! // 0, 1, 2, 3, 4, 5, 6, 2, 3, 4, 5, 6, 2, ...
+//! let step = |n: &mut u32| *n = if *n == 6 { 2 } else { *n + 1 };
+//! let cycle = find_cycle(&0, step);
+//! assert_eq!((cycle.start, cycle.len), (2, 5));
+//! assert_eq!(state_at(0, 1_000_000_000, step), 5);
+//! ```
+
+/// Where the states of a simulation start repeating: the state after `start`
+/// steps comes back every `len` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+  pub start: usize,
+  pub len: usize,
+}
+
+/// Finds the cycle in the states reached by applying `step` to `initial` over
+/// and over. Uses Brent's algorithm, so it only keeps a couple of states around
+/// instead of all of them. Loops forever if the states never repeat.
+pub fn find_cycle<S, F>(initial: &S, mut step: F) -> Cycle
+where
+  S: Clone + PartialEq,
+  F: FnMut(&mut S),
+{
+  // Find the length by moving the tortoise to the hare at every power of two
+  let mut power = 1;
+  let mut len = 1;
+  let mut tortoise = initial.clone();
+  let mut hare = initial.clone();
+  step(&mut hare);
+  while tortoise != hare {
+    if power == len {
+      tortoise = hare.clone();
+      power *= 2;
+      len = 0;
+    }
+    step(&mut hare);
+    len += 1;
+  }
+
+  // With the hare `len` steps ahead, they first meet at the start of the cycle
+  let mut tortoise = initial.clone();
+  let mut hare = initial.clone();
+  for _ in 0..len {
+    step(&mut hare);
+  }
+  let mut start = 0;
+  while tortoise != hare {
+    step(&mut tortoise);
+    step(&mut hare);
+    start += 1;
+  }
+
+  Cycle { start, len }
+}
+
+/// The state after `n` steps, without having to do all of them when the states
+/// start repeating. Stops at `n` if it gets there before finding the cycle, so
+/// the states don't even have to repeat when `n` is small.
+pub fn state_at<S, F>(initial: S, n: usize, mut step: F) -> S
+where
+  S: Clone + PartialEq,
+  F: FnMut(&mut S),
+{
+  if n == 0 {
+    return initial;
+  }
+  // The first half of `find_cycle`, while keeping count of the hare's steps
+  let mut power = 1;
+  let mut len = 1;
+  let mut tortoise = initial.clone();
+  let mut hare = initial;
+  step(&mut hare);
+  let mut steps = 1;
+  while tortoise != hare {
+    if steps == n {
+      return hare;
+    }
+    if power == len {
+      tortoise = hare.clone();
+      power *= 2;
+      len = 0;
+    }
+    step(&mut hare);
+    steps += 1;
+    len += 1;
+  }
+
+  // The hare is `len` steps ahead of the same state, so from here on the states
+  // repeat every `len` steps
+  for _ in 0..(n - steps) % len {
+    step(&mut hare);
+  }
+  hare
+}
@@ -0,0 +1,143 @@
+//! Compass directions for moving around a grid.
+//!
+//! ```
+//! use aoc_template::utils::{Direction, Pos};
+//!
+//! let dir = Direction::from_char('^').unwrap();
+//! assert_eq!(dir, Direction::N);
+//! assert_eq!(dir.turn_right(), Direction::E);
+//! assert_eq!(Pos::new(1, 1) + dir.to_pos(), Pos::new(1, 0));
+//! ```
+
+use crate::utils::Pos;
+use nom::character::complete::anychar;
+use nom::combinator::map_opt;
+use nom::IResult;
+
+/// A compass direction. North is up, which is towards negative y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+  N,
+  NE,
+  E,
+  SE,
+  S,
+  SW,
+  W,
+  NW,
+}
+
+use Direction::*;
+
+impl Direction {
+  pub const CARDINALS: [Direction; 4] = [N, E, S, W];
+
+  /// Clockwise, starting at north
+  pub const ALL: [Direction; 8] = [N, NE, E, SE, S, SW, W, NW];
+
+  pub fn cardinals() -> impl Iterator<Item = Direction> {
+    Direction::CARDINALS.into_iter()
+  }
+
+  pub fn all() -> impl Iterator<Item = Direction> {
+    Direction::ALL.into_iter()
+  }
+
+  /// The position in ALL, for storing something per direction
+  pub fn index(self) -> usize {
+    self as usize
+  }
+
+  /// The position in CARDINALS, or None for a diagonal direction
+  pub fn cardinal_index(self) -> Option<usize> {
+    Direction::CARDINALS.iter().position(|d| *d == self)
+  }
+
+  // Turns by a number of eighths of a full circle, clockwise
+  fn turn_eighths(self, eighths: usize) -> Direction {
+    Direction::ALL[(self.index() + eighths) % 8]
+  }
+
+  /// Turns 90 degrees clockwise
+  pub fn turn_right(self) -> Direction {
+    self.turn_eighths(2)
+  }
+
+  /// Turns 90 degrees counterclockwise
+  pub fn turn_left(self) -> Direction {
+    self.turn_eighths(6)
+  }
+
+  pub fn reverse(self) -> Direction {
+    self.turn_eighths(4)
+  }
+
+  pub fn is_horizontal(self) -> bool {
+    matches!(self, E | W)
+  }
+
+  pub fn is_vertical(self) -> bool {
+    matches!(self, N | S)
+  }
+
+  /// The step of one tile in this direction
+  pub fn to_pos(self) -> Pos {
+    let (x, y) = match self {
+      N => (0, -1),
+      NE => (1, -1),
+      E => (1, 0),
+      SE => (1, 1),
+      S => (0, 1),
+      SW => (-1, 1),
+      W => (-1, 0),
+      NW => (-1, -1),
+    };
+    Pos::new(x, y)
+  }
+
+  /// The inverse of `to_pos`. Returns None for anything but a single step.
+  pub fn from_pos(pos: Pos) -> Option<Direction> {
+    Direction::all().find(|d| d.to_pos() == pos)
+  }
+
+  /// Understands up/down/left/right, compass points, and arrows
+  pub fn from_char(c: char) -> Option<Direction> {
+    match c {
+      'U' | 'N' | '^' | '↑' => Some(N),
+      'R' | 'E' | '>' | '→' => Some(E),
+      'D' | 'S' | 'v' | '↓' => Some(S),
+      'L' | 'W' | '<' | '←' => Some(W),
+      '↗' => Some(NE),
+      '↘' => Some(SE),
+      '↙' => Some(SW),
+      '↖' => Some(NW),
+      _ => None,
+    }
+  }
+}
+
+impl From<Direction> for Pos {
+  fn from(d: Direction) -> Pos {
+    d.to_pos()
+  }
+}
+
+impl TryFrom<Pos> for Direction {
+  type Error = Pos;
+
+  fn try_from(pos: Pos) -> Result<Direction, Pos> {
+    Direction::from_pos(pos).ok_or(pos)
+  }
+}
+
+impl TryFrom<char> for Direction {
+  type Error = char;
+
+  fn try_from(c: char) -> Result<Direction, char> {
+    Direction::from_char(c).ok_or(c)
+  }
+}
+
+pub fn parse_direction(input: &str) -> IResult<&str, Direction> {
+  map_opt(anychar, Direction::from_char)(input)
+}
@@ -0,0 +1,350 @@
+//! Graphs with labelled nodes.
+//!
+//! ```
+//! use aoc_template::utils::Graph;
+//!
+//! let mut g = Graph::directed();
+//! g.add_edge("shirt", "tie", 1);
+//! g.add_edge("tie", "jacket", 1);
+//! g.add_edge("shirt", "belt", 1);
+//! let order = g.topological_sort().unwrap();
+//! assert_eq!(*g.label(order[0]), "shirt");
+//! let dists = g.bfs(g.id(&"shirt").unwrap());
+//! assert_eq!(dists[g.id(&"jacket").unwrap()], Some(2));
+//! ```
+
+use crate::utils::HashMap;
+use std::collections::VecDeque;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+
+pub type NodeId = usize;
+
+/// A graph of which the nodes are labelled with something like a name or a
+/// position. Labels are interned: every label gets a NodeId, in the order they
+/// were first added, and all queries work on those ids.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+  directed: bool,
+  labels: Vec<N>,
+  ids: HashMap<N, NodeId>,
+  // The outgoing edges of every node with their weights. In an undirected graph
+  // every edge is stored at both of its ends.
+  edges: Vec<Vec<(NodeId, usize)>>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+  pub fn directed() -> Self {
+    Graph {
+      directed: true,
+      labels: vec![],
+      ids: HashMap::default(),
+      edges: vec![],
+    }
+  }
+
+  pub fn undirected() -> Self {
+    Graph {
+      directed: false,
+      ..Graph::directed()
+    }
+  }
+
+  pub fn is_directed(&self) -> bool {
+    self.directed
+  }
+
+  /// Adds a node if there is none with this label yet
+  pub fn add_node(&mut self, label: N) -> NodeId {
+    if let Some(id) = self.ids.get(&label) {
+      return *id;
+    }
+    let id = self.labels.len();
+    self.labels.push(label.clone());
+    self.ids.insert(label, id);
+    self.edges.push(vec![]);
+    id
+  }
+
+  /// Adds the nodes too if they aren't in the graph yet
+  pub fn add_edge(&mut self, from: N, to: N, weight: usize) {
+    let from = self.add_node(from);
+    let to = self.add_node(to);
+    self.add_edge_between(from, to, weight);
+  }
+
+  pub fn add_edge_between(&mut self, from: NodeId, to: NodeId, weight: usize) {
+    self.edges[from].push((to, weight));
+    if !self.directed && from != to {
+      self.edges[to].push((from, weight));
+    }
+  }
+
+  /// Removes all edges from one node to another, and back if undirected
+  pub fn remove_edge(&mut self, from: NodeId, to: NodeId) {
+    self.edges[from].retain(|(n, _)| *n != to);
+    if !self.directed {
+      self.edges[to].retain(|(n, _)| *n != from);
+    }
+  }
+
+  pub fn id(&self, label: &N) -> Option<NodeId> {
+    self.ids.get(label).copied()
+  }
+
+  pub fn label(&self, id: NodeId) -> &N {
+    &self.labels[id]
+  }
+
+  pub fn len(&self) -> usize {
+    self.labels.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.labels.is_empty()
+  }
+
+  pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+    0..self.len()
+  }
+
+  /// The nodes that an edge goes to from this node, with the edge weights
+  pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+    self.edges[id].iter().copied()
+  }
+
+  pub fn out_degree(&self, id: NodeId) -> usize {
+    self.edges[id].len()
+  }
+
+  pub fn in_degree(&self, id: NodeId) -> usize {
+    if self.directed {
+      self
+        .edges
+        .iter()
+        .map(|es| es.iter().filter(|(n, _)| *n == id).count())
+        .sum()
+    } else {
+      self.out_degree(id)
+    }
+  }
+
+  /// The same as the in and out degree in an undirected graph
+  pub fn degree(&self, id: NodeId) -> usize {
+    assert!(!self.directed, "Use in_degree or out_degree for a directed graph");
+    self.out_degree(id)
+  }
+
+  /// The number of edges from the start to every node, ignoring the weights, or
+  /// None for the nodes that can't be reached
+  pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+    let mut dists = vec![None; self.len()];
+    dists[start] = Some(0);
+    let mut q = VecDeque::from([start]);
+    while let Some(id) = q.pop_front() {
+      let dist = dists[id].unwrap();
+      for (n, _) in self.neighbours(id) {
+        if dists[n].is_none() {
+          dists[n] = Some(dist + 1);
+          q.push_back(n);
+        }
+      }
+    }
+    dists
+  }
+
+  /// All nodes that can be reached from the start, depth first
+  pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+    let mut seen = vec![false; self.len()];
+    let mut res = vec![];
+    let mut stack = vec![start];
+    while let Some(id) = stack.pop() {
+      if !seen[id] {
+        seen[id] = true;
+        res.push(id);
+        // Reversed, so the first neighbour is visited first
+        stack.extend(self.edges[id].iter().rev().map(|(n, _)| *n));
+      }
+    }
+    res
+  }
+
+  /// Groups of nodes that are connected, ignoring the direction of the edges
+  pub fn components(&self) -> Vec<Vec<NodeId>> {
+    let mut undirected = self.edges.clone();
+    if self.directed {
+      for (from, es) in self.edges.iter().enumerate() {
+        for (to, w) in es {
+          undirected[*to].push((from, *w));
+        }
+      }
+    }
+    let mut component = vec![None; self.len()];
+    let mut res: Vec<Vec<NodeId>> = vec![];
+    for start in self.nodes() {
+      if component[start].is_some() {
+        continue;
+      }
+      let c = res.len();
+      component[start] = Some(c);
+      let mut nodes = vec![];
+      let mut stack = vec![start];
+      while let Some(id) = stack.pop() {
+        nodes.push(id);
+        for (n, _) in undirected[id].iter() {
+          if component[*n].is_none() {
+            component[*n] = Some(c);
+            stack.push(*n);
+          }
+        }
+      }
+      res.push(nodes);
+    }
+    res
+  }
+
+  /// Groups of nodes that can all reach each other, using Tarjan's algorithm. A
+  /// group comes after all groups that it has edges to.
+  pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+    struct Tarjan<'a> {
+      edges: &'a [Vec<(NodeId, usize)>],
+      index: Vec<Option<usize>>,
+      low: Vec<usize>,
+      on_stack: Vec<bool>,
+      stack: Vec<NodeId>,
+      next_index: usize,
+      res: Vec<Vec<NodeId>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+      fn visit(&mut self, id: NodeId) {
+        self.index[id] = Some(self.next_index);
+        self.low[id] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack[id] = true;
+        for (n, _) in self.edges[id].iter() {
+          match self.index[*n] {
+            None => {
+              self.visit(*n);
+              self.low[id] = self.low[id].min(self.low[*n]);
+            }
+            Some(i) if self.on_stack[*n] => self.low[id] = self.low[id].min(i),
+            _ => {}
+          }
+        }
+        if Some(self.low[id]) == self.index[id] {
+          let mut component = vec![];
+          loop {
+            let n = self.stack.pop().unwrap();
+            self.on_stack[n] = false;
+            component.push(n);
+            if n == id {
+              break;
+            }
+          }
+          self.res.push(component);
+        }
+      }
+    }
+
+    let mut tarjan = Tarjan {
+      edges: &self.edges,
+      index: vec![None; self.len()],
+      low: vec![0; self.len()],
+      on_stack: vec![false; self.len()],
+      stack: vec![],
+      next_index: 0,
+      res: vec![],
+    };
+    for id in self.nodes() {
+      if tarjan.index[id].is_none() {
+        tarjan.visit(id);
+      }
+    }
+    tarjan.res
+  }
+
+  /// An order of the nodes in which every edge goes forward, or None if there is
+  /// a cycle
+  pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+    assert!(self.directed, "Only a directed graph can be sorted topologically");
+    let mut in_degrees = vec![0; self.len()];
+    for (n, _) in self.edges.iter().flatten() {
+      in_degrees[*n] += 1;
+    }
+    let mut q: VecDeque<NodeId> = self.nodes().filter(|id| in_degrees[*id] == 0).collect();
+    let mut res = vec![];
+    while let Some(id) = q.pop_front() {
+      res.push(id);
+      for (n, _) in self.neighbours(id) {
+        in_degrees[n] -= 1;
+        if in_degrees[n] == 0 {
+          q.push_back(n);
+        }
+      }
+    }
+    (res.len() == self.len()).then_some(res)
+  }
+
+  /// Replaces every path through nodes with exactly two neighbours by a single
+  /// edge with the total weight, like corridors in a maze. A path that leaves a
+  /// node and comes back to it becomes an edge from that node to itself. Nodes on
+  /// a cycle without any other nodes are dropped.
+  pub fn contract_paths(&self) -> Graph<N> {
+    assert!(!self.directed, "Only paths in an undirected graph can be contracted");
+    let is_corridor = |id: NodeId| self.degree(id) == 2;
+    let mut res = Graph::undirected();
+    for id in self.nodes().filter(|id| !is_corridor(*id)) {
+      res.add_node(self.labels[id].clone());
+    }
+    for from in self.nodes().filter(|id| !is_corridor(*id)) {
+      for (next, weight) in self.neighbours(from) {
+        let (mut prev, mut cur, mut total) = (from, next, weight);
+        while is_corridor(cur) {
+          let (n, w) = self.neighbours(cur).find(|(n, _)| *n != prev).unwrap_or(self.edges[cur][0]);
+          (prev, cur, total) = (cur, n, total + w);
+        }
+        // Every path is found from both ends, so only add it from one of them.
+        // Both ends of a loop are the same node, but it starts and ends with
+        // different corridor nodes.
+        if from < cur || (from == cur && next <= prev) {
+          res.add_edge(self.labels[from].clone(), self.labels[cur].clone(), total);
+        }
+      }
+    }
+    res
+  }
+}
+
+impl<N: Clone + Eq + Hash + Display> Graph<N> {
+  /// The graph in Graphviz's DOT format, for example to view it with
+  /// `dot -Tsvg graph.dot > graph.svg`. Weights other than 1 are shown as edge
+  /// labels.
+  pub fn to_dot(&self) -> String {
+    let (kind, arrow) = if self.directed {
+      ("digraph", "->")
+    } else {
+      ("graph", "--")
+    };
+    let mut res = format!("{} {{\n", kind);
+    for (id, label) in self.labels.iter().enumerate() {
+      writeln!(res, "  {} [label=\"{}\"];", id, label.to_string().replace('"', "\\\"")).unwrap();
+    }
+    for (from, es) in self.edges.iter().enumerate() {
+      for (to, weight) in es {
+        // Undirected edges are stored twice, but should be drawn once
+        if !self.directed && *to < from {
+          continue;
+        }
+        if *weight == 1 {
+          writeln!(res, "  {} {} {};", from, arrow, to).unwrap();
+        } else {
+          writeln!(res, "  {} {} {} [label=\"{}\"];", from, arrow, to, weight).unwrap();
+        }
+      }
+    }
+    res.push_str("}\n");
+    res
+  }
+}
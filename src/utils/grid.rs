@@ -0,0 +1,208 @@
+//! Rectangular grids, like most puzzle inputs.
+//!
+//! ```
+//! use aoc_template::utils::{Grid, Pos};
+//!
+//! let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+//! assert_eq!((grid.width(), grid.height()), (3, 2));
+//! assert_eq!(grid[Pos::new(1, 1)], 5);
+//! assert_eq!(grid.find(|v| *v == 6), Some(Pos::new(2, 1)));
+//! assert_eq!(grid.neighbours_4(Pos::new(0, 0)).count(), 2);
+//! ```
+
+use crate::utils::{Direction, HashMap, Pos};
+use nom::character::complete::newline;
+use nom::error::{ErrorKind, ParseError};
+use nom::{
+  multi::{many1, separated_list1},
+  IResult,
+};
+use nom::{AsChar, InputIter, InputLength, Parser, Slice};
+use std::ops::{Index, IndexMut, RangeFrom};
+
+/// A rectangular grid, stored row by row. Positions are (x, y) with (0, 0) in the
+/// top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+/// A grid that only stores some of its positions, for when most of them are
+/// empty, or when it has no fixed bounds.
+pub type SparseGrid<A> = HashMap<Pos, A>;
+
+impl<T> Grid<T> {
+  pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+
+  /// Panics if the rows don't all have the same length
+  pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+    let height = rows.len();
+    let width = rows.first().map_or(0, |r| r.len());
+    assert!(
+      rows.iter().all(|r| r.len() == width),
+      "All rows of a grid must have the same length"
+    );
+    Grid {
+      width,
+      height,
+      cells: rows.into_iter().flatten().collect(),
+    }
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  /// The bottom right position
+  pub fn max_pos(&self) -> Pos {
+    Pos::new(self.width as i32 - 1, self.height as i32 - 1)
+  }
+
+  pub fn contains(&self, pos: Pos) -> bool {
+    pos.x() >= 0 && pos.y() >= 0 && (pos.x() as usize) < self.width && (pos.y() as usize) < self.height
+  }
+
+  fn index_of(&self, pos: Pos) -> Option<usize> {
+    self
+      .contains(pos)
+      .then(|| pos.y() as usize * self.width + pos.x() as usize)
+  }
+
+  pub fn get(&self, pos: Pos) -> Option<&T> {
+    self.index_of(pos).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+    self.index_of(pos).map(|i| &mut self.cells[i])
+  }
+
+  /// All positions in the grid, row by row
+  pub fn positions(&self) -> impl Iterator<Item = Pos> {
+    let width = self.width;
+    (0..self.height).flat_map(move |y| {
+      (0..width).map(move |x| Pos::new(x as i32, y as i32))
+    })
+  }
+
+  /// All cells with their positions, row by row
+  pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+    self.positions().zip(self.cells.iter())
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+    self.positions().zip(self.cells.iter_mut())
+  }
+
+  /// The first position, row by row, of which the cell satisfies the predicate
+  pub fn find<F>(&self, mut predicate: F) -> Option<Pos>
+  where
+    F: FnMut(&T) -> bool,
+  {
+    self.iter().find(|(_, t)| predicate(t)).map(|(p, _)| p)
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // chunks panics on a chunk size of 0
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|x| self.column(x))
+  }
+
+  /// The horizontal and vertical neighbours of a position that are in the grid
+  pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    Direction::cardinals()
+      .map(move |d| pos + d.to_pos())
+      .filter(|p| self.contains(*p))
+  }
+
+  /// Like neighbours_4, but including the diagonal neighbours
+  pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    Direction::all()
+      .map(move |d| pos + d.to_pos())
+      .filter(|p| self.contains(*p))
+  }
+
+  pub fn map<U, F>(&self, f: F) -> Grid<U>
+  where
+    F: FnMut(&T) -> U,
+  {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  pub fn to_sparse(&self) -> SparseGrid<T>
+  where
+    T: Clone,
+  {
+    self.iter().map(|(p, t)| (p, t.clone())).collect()
+  }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: Pos) -> &T {
+    self
+      .get(pos)
+      .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+  }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+  fn index_mut(&mut self, pos: Pos) -> &mut T {
+    self
+      .get_mut(pos)
+      .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+  }
+}
+
+/// Parses rows of cells separated by newlines, where every cell is parsed by the
+/// given parser. Fails if the rows don't all have the same length.
+pub fn parse_grid<I, O, E, F>(mut one_char_parser: F) -> impl FnMut(I) -> IResult<I, Grid<O>, E>
+  where I: Slice<RangeFrom<usize>> + InputIter + Clone + InputLength,
+        <I as InputIter>::Item: AsChar,
+        F: Parser<I, O, E>,
+        E: ParseError<I>,
+{ move |input: I| {
+    let (rest, rows) = separated_list1(newline, many1(|x| one_char_parser.parse(x)))(input.clone())?;
+    if rows.iter().any(|r| r.len() != rows[0].len()) {
+      return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Verify)));
+    }
+    Ok((rest, Grid::from_rows(rows)))
+  }
+}
+
+/// The largest x and y of any position in a sparse grid
+pub fn grid_max_dims<A>(grid: &SparseGrid<A>) -> (i32, i32) {
+  grid.iter().fold((0, 0), |(maxx, maxy), (p, _)| {
+    (i32::max(maxx, p.x()), i32::max(maxy, p.y()))
+  })
+}
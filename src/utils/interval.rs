@@ -0,0 +1,261 @@
+//! Ranges of numbers, and sets and boxes made of them.
+//!
+//! ```
+//! use aoc_template::utils::{Interval, RangeSet};
+//!
+//! let (left, right) = Interval::new(0, 10).split_at(4);
+//! assert_eq!((left.len(), right.len()), (4, 6));
+//!
+//! let set: RangeSet<i64> = [Interval::new(0, 5), Interval::new(5, 8), Interval::new(20, 30)]
+//!   .into_iter()
+//!   .collect();
+//! assert_eq!(set.intervals().len(), 2);
+//! assert_eq!(set.len(), 18);
+//! assert!(set.contains(25) && !set.contains(8));
+//! ```
+
+use num::PrimInt;
+
+/// The numbers from `start` up to but not including `end`. An interval of which
+/// the end is not after the start is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+  pub start: T,
+  pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+  pub fn new(start: T, end: T) -> Self {
+    Interval { start, end }
+  }
+
+  /// The numbers from `first` up to and including `last`
+  pub fn inclusive(first: T, last: T) -> Self {
+    Interval::new(first, last + T::one())
+  }
+
+  pub fn with_len(start: T, len: T) -> Self {
+    Interval::new(start, start + len)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.end <= self.start
+  }
+
+  pub fn len(&self) -> T {
+    if self.is_empty() {
+      T::zero()
+    } else {
+      self.end - self.start
+    }
+  }
+
+  /// The last number in the interval, if there is one
+  pub fn last(&self) -> Option<T> {
+    (!self.is_empty()).then(|| self.end - T::one())
+  }
+
+  pub fn contains(&self, x: T) -> bool {
+    self.start <= x && x < self.end
+  }
+
+  pub fn overlaps(&self, other: &Self) -> bool {
+    !self.intersection(other).is_empty()
+  }
+
+  pub fn intersection(&self, other: &Self) -> Self {
+    Interval::new(self.start.max(other.start), self.end.min(other.end))
+  }
+
+  /// The part before `x` and the part from `x` onwards. Either can be empty.
+  pub fn split_at(&self, x: T) -> (Self, Self) {
+    let x = x.max(self.start).min(self.end.max(self.start));
+    (Interval::new(self.start, x), Interval::new(x, self.end))
+  }
+
+  pub fn shift(&self, offset: T) -> Self {
+    Interval::new(self.start + offset, self.end + offset)
+  }
+
+  /// Moves the interval so that `from` ends up at `to`. Unlike `shift`, this also
+  /// moves unsigned intervals down.
+  pub fn map_onto(&self, from: T, to: T) -> Self {
+    if to >= from {
+      self.shift(to - from)
+    } else {
+      Interval::new(self.start - (from - to), self.end - (from - to))
+    }
+  }
+}
+
+/// A set of numbers, stored as sorted intervals that don't overlap or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T> {
+  intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> RangeSet<T> {
+  pub fn new() -> Self {
+    RangeSet { intervals: vec![] }
+  }
+
+  pub fn intervals(&self) -> &[Interval<T>] {
+    &self.intervals
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.intervals.is_empty()
+  }
+
+  /// The amount of numbers in the set
+  pub fn len(&self) -> T {
+    self
+      .intervals
+      .iter()
+      .fold(T::zero(), |acc, i| acc + i.len())
+  }
+
+  pub fn min(&self) -> Option<T> {
+    self.intervals.first().map(|i| i.start)
+  }
+
+  pub fn max(&self) -> Option<T> {
+    self.intervals.last().and_then(|i| i.last())
+  }
+
+  pub fn contains(&self, x: T) -> bool {
+    self.intervals.iter().any(|i| i.contains(x))
+  }
+
+  pub fn insert(&mut self, interval: Interval<T>) {
+    if interval.is_empty() {
+      return;
+    }
+    // Merge with everything it overlaps or touches
+    let mut merged = interval;
+    self.intervals.retain(|i| {
+      if i.start <= merged.end && merged.start <= i.end {
+        merged = Interval::new(merged.start.min(i.start), merged.end.max(i.end));
+        false
+      } else {
+        true
+      }
+    });
+    let at = self.intervals.partition_point(|i| i.start < merged.start);
+    self.intervals.insert(at, merged);
+  }
+
+  pub fn union(&self, other: &Self) -> Self {
+    let mut res = self.clone();
+    for i in other.intervals.iter() {
+      res.insert(*i);
+    }
+    res
+  }
+
+  pub fn intersection(&self, other: &Self) -> Self {
+    self
+      .intervals
+      .iter()
+      .flat_map(|a| other.intervals.iter().map(|b| a.intersection(b)))
+      .collect()
+  }
+
+  pub fn difference(&self, other: &Self) -> Self {
+    let mut res = self.intervals.clone();
+    for b in other.intervals.iter() {
+      res = res
+        .iter()
+        .flat_map(|a| {
+          let (before, _) = a.split_at(b.start);
+          let (_, after) = a.split_at(b.end);
+          [before, after]
+        })
+        .filter(|i| !i.is_empty())
+        .collect();
+    }
+    RangeSet { intervals: res }
+  }
+
+  /// The numbers before `x` and the numbers from `x` onwards
+  pub fn split_at(&self, x: T) -> (Self, Self) {
+    self
+      .intervals
+      .iter()
+      .map(|i| i.split_at(x))
+      .fold((RangeSet::new(), RangeSet::new()), |(mut lo, mut hi), (l, h)| {
+        lo.insert(l);
+        hi.insert(h);
+        (lo, hi)
+      })
+  }
+
+  pub fn shift(&self, offset: T) -> Self {
+    self.intervals.iter().map(|i| i.shift(offset)).collect()
+  }
+
+  pub fn map_onto(&self, from: T, to: T) -> Self {
+    self.intervals.iter().map(|i| i.map_onto(from, to)).collect()
+  }
+
+  /// Moves every number that is in one of the pieces' source intervals to where
+  /// that interval starts over, and leaves the other numbers where they are. The
+  /// source intervals should not overlap.
+  pub fn map_pieces<I>(&self, pieces: I) -> Self
+  where
+    I: IntoIterator<Item = (Interval<T>, T)>,
+  {
+    let mut rest = self.clone();
+    let mut res = RangeSet::new();
+    for (source, new_start) in pieces {
+      let piece: RangeSet<T> = [source].into_iter().collect();
+      let moved = rest.intersection(&piece).map_onto(source.start, new_start);
+      res = res.union(&moved);
+      rest = rest.difference(&piece);
+    }
+    res.union(&rest)
+  }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for RangeSet<T> {
+  fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+    let mut res = RangeSet::new();
+    for i in iter {
+      res.insert(i);
+    }
+    res
+  }
+}
+
+/// All combinations of one number from each interval, like a rectangle or a cube
+/// when N is 2 or 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: PrimInt, const N: usize> IntervalBox<T, N> {
+  pub fn is_empty(&self) -> bool {
+    self.0.iter().any(|i| i.is_empty())
+  }
+
+  /// The amount of combinations. Panics if that doesn't fit in a usize.
+  pub fn volume(&self) -> usize {
+    self
+      .0
+      .iter()
+      .map(|i| i.len().to_usize().unwrap())
+      .product()
+  }
+
+  pub fn intersection(&self, other: &Self) -> Self {
+    IntervalBox(std::array::from_fn(|a| self.0[a].intersection(&other.0[a])))
+  }
+
+  /// Cuts the box in two along one of its axes
+  pub fn split_at(&self, axis: usize, x: T) -> (Self, Self) {
+    let (lo, hi) = self.0[axis].split_at(x);
+    let (mut lo_box, mut hi_box) = (*self, *self);
+    lo_box.0[axis] = lo;
+    hi_box.0[axis] = hi;
+    (lo_box, hi_box)
+  }
+}
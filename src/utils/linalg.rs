@@ -0,0 +1,346 @@
+//! Linear algebra and line intersections with exact rational numbers.
+//!
+//! ```
+//! use aoc_template::utils::{rational, Intersection, Line, Matrix};
+//! use aoc_template::utils::Point2;
+//!
+//! // x + y = 3 and x - y = 1
+//! let m = Matrix::from_rows(&[vec![1, 1], vec![1, -1]]);
+//! assert_eq!(m.solve(&[rational(3), rational(1)]), Ok(vec![rational(2), rational(1)]));
+//!
+//! let a = Line::segment(Point2::new(0, 0), Point2::new(4, 4));
+//! let b = Line::segment(Point2::new(0, 4), Point2::new(4, 0));
+//! match a.intersect_segment(&b) {
+//!   Intersection::Point { at, .. } => assert_eq!(at, [rational(2), rational(2)]),
+//!   other => panic!("Expected a point, got {:?}", other),
+//! }
+//! ```
+
+use crate::utils::Point;
+use num::{BigInt, BigRational, One, Signed, Zero};
+use std::array;
+use std::fmt;
+use std::ops;
+
+/// Exact arithmetic for when floats lose precision, like with coordinates in the
+/// hundreds of trillions. Everything is a big rational, so nothing overflows.
+pub type Rational = BigRational;
+
+pub fn rational<T: Into<BigInt>>(n: T) -> Rational {
+  Rational::from_integer(n.into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinalgError {
+  DimensionMismatch,
+  /// The equations don't pin down a single solution
+  Singular,
+  /// The equations contradict each other
+  Inconsistent,
+}
+
+impl fmt::Display for LinalgError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      LinalgError::DimensionMismatch => write!(f, "The dimensions don't match"),
+      LinalgError::Singular => write!(f, "There is no unique solution"),
+      LinalgError::Inconsistent => write!(f, "There is no solution"),
+    }
+  }
+}
+
+/// A small dense matrix, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+  rows: usize,
+  cols: usize,
+  cells: Vec<Rational>,
+}
+
+impl Matrix {
+  pub fn zeros(rows: usize, cols: usize) -> Self {
+    Matrix {
+      rows,
+      cols,
+      cells: vec![Rational::zero(); rows * cols],
+    }
+  }
+
+  pub fn identity(n: usize) -> Self {
+    let mut res = Matrix::zeros(n, n);
+    for i in 0..n {
+      res[(i, i)] = Rational::one();
+    }
+    res
+  }
+
+  /// Panics if the rows differ in length
+  pub fn from_rows<T: Into<BigInt> + Copy>(rows: &[Vec<T>]) -> Self {
+    let cols = rows.first().map_or(0, |r| r.len());
+    assert!(rows.iter().all(|r| r.len() == cols), "The rows of a matrix differ in length");
+    Matrix {
+      rows: rows.len(),
+      cols,
+      cells: rows.iter().flatten().map(|c| rational(*c)).collect(),
+    }
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  fn swap_rows(&mut self, a: usize, b: usize) {
+    for c in 0..self.cols {
+      self.cells.swap(a * self.cols + c, b * self.cols + c);
+    }
+  }
+
+  // Turns the matrix into reduced row echelon form with Gaussian elimination.
+  // Returns the columns that got a pivot, and whether rows were swapped an odd
+  // number of times. Any non-zero pivot is fine, since nothing gets rounded.
+  fn reduce(&mut self) -> (Vec<usize>, bool) {
+    let mut pivots = vec![];
+    let mut odd_swaps = false;
+    for c in 0..self.cols {
+      let r = pivots.len();
+      if r == self.rows {
+        break;
+      }
+      let Some(p) = (r..self.rows).find(|p| !self[(*p, c)].is_zero()) else {
+        continue;
+      };
+      if p != r {
+        self.swap_rows(p, r);
+        odd_swaps = !odd_swaps;
+      }
+      for other in 0..self.rows {
+        if other == r || self[(other, c)].is_zero() {
+          continue;
+        }
+        let factor = &self[(other, c)] / &self[(r, c)];
+        for k in c..self.cols {
+          let delta = &factor * &self[(r, k)];
+          self[(other, k)] -= delta;
+        }
+      }
+      pivots.push(c);
+    }
+    (pivots, odd_swaps)
+  }
+
+  pub fn rank(&self) -> usize {
+    self.clone().reduce().0.len()
+  }
+
+  pub fn determinant(&self) -> Result<Rational, LinalgError> {
+    if self.rows != self.cols {
+      return Err(LinalgError::DimensionMismatch);
+    }
+    let mut m = self.clone();
+    let (pivots, odd_swaps) = m.reduce();
+    if pivots.len() < self.rows {
+      return Ok(Rational::zero());
+    }
+    // Clearing the other rows doesn't change the diagonal
+    let det = (0..self.rows).fold(Rational::one(), |acc, i| acc * &m[(i, i)]);
+    Ok(if odd_swaps { -det } else { det })
+  }
+
+  /// The x for which self * x = b. There can be more equations than unknowns, as
+  /// long as they agree with each other.
+  pub fn solve(&self, b: &[Rational]) -> Result<Vec<Rational>, LinalgError> {
+    if b.len() != self.rows {
+      return Err(LinalgError::DimensionMismatch);
+    }
+    let mut augmented = Matrix::zeros(self.rows, self.cols + 1);
+    for r in 0..self.rows {
+      for c in 0..self.cols {
+        augmented[(r, c)] = self[(r, c)].clone();
+      }
+      augmented[(r, self.cols)] = b[r].clone();
+    }
+    let (pivots, _) = augmented.reduce();
+    if pivots.last() == Some(&self.cols) {
+      return Err(LinalgError::Inconsistent);
+    }
+    if pivots.len() < self.cols {
+      return Err(LinalgError::Singular);
+    }
+    Ok(
+      pivots
+        .iter()
+        .enumerate()
+        .map(|(r, c)| &augmented[(r, self.cols)] / &augmented[(r, *c)])
+        .collect(),
+    )
+  }
+
+  pub fn mul_vec(&self, v: &[Rational]) -> Result<Vec<Rational>, LinalgError> {
+    if v.len() != self.cols {
+      return Err(LinalgError::DimensionMismatch);
+    }
+    Ok(
+      (0..self.rows)
+        .map(|r| (0..self.cols).map(|c| &self[(r, c)] * &v[c]).sum())
+        .collect(),
+    )
+  }
+}
+
+impl ops::Index<(usize, usize)> for Matrix {
+  type Output = Rational;
+
+  fn index(&self, (r, c): (usize, usize)) -> &Self::Output {
+    assert!(r < self.rows && c < self.cols, "({}, {}) is outside of the matrix", r, c);
+    &self.cells[r * self.cols + c]
+  }
+}
+
+impl ops::IndexMut<(usize, usize)> for Matrix {
+  fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Self::Output {
+    assert!(r < self.rows && c < self.cols, "({}, {}) is outside of the matrix", r, c);
+    &mut self.cells[r * self.cols + c]
+  }
+}
+
+/// The points start + t * dir. As a segment, t goes from 0 to 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line<const N: usize> {
+  pub start: [Rational; N],
+  pub dir: [Rational; N],
+}
+
+pub type Line2 = Line<2>;
+pub type Line3 = Line<3>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intersection<const N: usize> {
+  /// A single point, at t along the first line and at u along the second
+  Point { at: [Rational; N], t: Rational, u: Rational },
+  /// The lines are the same, or the segments overlap in more than one point
+  Overlapping,
+  Parallel,
+  /// The lines aren't parallel but don't meet, which only happens in 3D
+  Skew,
+  /// Segments of which the lines meet, but not within both segments
+  Apart,
+  /// One of the lines has no direction, so it's only a point
+  NoDirection,
+}
+
+impl<const N: usize> Line<N> {
+  pub fn new<T: Into<BigInt> + Copy>(start: Point<T, N>, dir: Point<T, N>) -> Self {
+    Line {
+      start: start.0.map(rational),
+      dir: dir.0.map(rational),
+    }
+  }
+
+  /// The segment from a to b
+  pub fn segment<T: Into<BigInt> + Copy>(a: Point<T, N>, b: Point<T, N>) -> Self {
+    let start = a.0.map(rational);
+    let end = b.0.map(rational);
+    let dir = array::from_fn(|i| &end[i] - &start[i]);
+    Line { start, dir }
+  }
+
+  pub fn at(&self, t: &Rational) -> [Rational; N] {
+    array::from_fn(|i| &self.start[i] + &self.dir[i] * t)
+  }
+
+  pub fn has_direction(&self) -> bool {
+    self.dir.iter().any(|d| !d.is_zero())
+  }
+
+  /// The t at which the point is on the line, if it is. None on a line without a
+  /// direction, as there every t gives the same point.
+  pub fn param_of(&self, point: &[Rational; N]) -> Option<Rational> {
+    let axis = (0..N).find(|i| !self.dir[*i].is_zero())?;
+    let t = (&point[axis] - &self.start[axis]) / &self.dir[axis];
+    (self.at(&t) == *point).then_some(t)
+  }
+
+  pub fn is_parallel_to(&self, other: &Line<N>) -> bool {
+    parallel(&self.dir, &other.dir)
+  }
+
+  pub fn intersect(&self, other: &Line<N>) -> Intersection<N> {
+    if !self.has_direction() || !other.has_direction() {
+      return Intersection::NoDirection;
+    }
+    if self.is_parallel_to(other) {
+      return if self.param_of(&other.start).is_some() {
+        Intersection::Overlapping
+      } else {
+        Intersection::Parallel
+      };
+    }
+    // Solve start + t * dir = other.start + u * other.dir for t and u with the
+    // first two axes on which the directions aren't parallel
+    let (i, j, det) = (0..N)
+      .flat_map(|i| (i + 1..N).map(move |j| (i, j)))
+      .map(|(i, j)| (i, j, &other.dir[i] * &self.dir[j] - &self.dir[i] * &other.dir[j]))
+      .find(|(_, _, det)| !det.is_zero())
+      .unwrap();
+    let di = &other.start[i] - &self.start[i];
+    let dj = &other.start[j] - &self.start[j];
+    let t = (&other.dir[i] * &dj - &other.dir[j] * &di) / &det;
+    let u = (&self.dir[i] * &dj - &self.dir[j] * &di) / &det;
+    let at = self.at(&t);
+    if at != other.at(&u) {
+      return Intersection::Skew;
+    }
+    Intersection::Point { at, t, u }
+  }
+
+  /// Like intersect, but for the segments from t = 0 to t = 1
+  pub fn intersect_segment(&self, other: &Line<N>) -> Intersection<N> {
+    let in_segment = |t: &Rational| !t.is_negative() && *t <= Rational::one();
+    match self.intersect(other) {
+      Intersection::Point { at, t, u } => {
+        if in_segment(&t) && in_segment(&u) {
+          Intersection::Point { at, t, u }
+        } else {
+          Intersection::Apart
+        }
+      }
+      Intersection::Overlapping => {
+        // Where the other segment starts and ends on this one
+        let t0 = self.param_of(&other.start).unwrap();
+        let t1 = self.param_of(&other.at(&Rational::one())).unwrap();
+        let (lo, hi) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+        if hi.is_negative() || lo > Rational::one() {
+          Intersection::Apart
+        } else if hi.is_zero() {
+          let u = other.param_of(&self.start).unwrap();
+          Intersection::Point { at: self.start.clone(), t: hi, u }
+        } else if lo == Rational::one() {
+          let at = self.at(&lo);
+          let u = other.param_of(&at).unwrap();
+          Intersection::Point { at, t: lo, u }
+        } else {
+          Intersection::Overlapping
+        }
+      }
+      other => other,
+    }
+  }
+}
+
+// Whether two vectors point the same or the opposite way, or one of them is zero
+fn parallel<const N: usize>(a: &[Rational; N], b: &[Rational; N]) -> bool {
+  (0..N).all(|i| (i + 1..N).all(|j| &a[i] * &b[j] == &a[j] * &b[i]))
+}
+
+/// The cross product of two 3D vectors
+pub fn cross(a: &[Rational; 3], b: &[Rational; 3]) -> [Rational; 3] {
+  [
+    &a[1] * &b[2] - &a[2] * &b[1],
+    &a[2] * &b[0] - &a[0] * &b[2],
+    &a[0] * &b[1] - &a[1] * &b[0],
+  ]
+}
@@ -0,0 +1,135 @@
+//! Number theory for puzzles about things that repeat.
+//!
+//! ```
+//! use aoc_template::utils::{crt, lcm_all};
+//!
+//! assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+//! // x = 2 (mod 3), x = 3 (mod 5) and x = 2 (mod 7)
+//! assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+//! ```
+
+use num::{CheckedMul, Integer};
+use std::fmt;
+
+/// The gcd of all numbers, or 0 if there are none
+pub fn gcd_all<T, I>(nrs: I) -> T
+where
+  T: Integer + Copy,
+  I: IntoIterator<Item = T>,
+{
+  nrs.into_iter().fold(T::zero(), |acc, n| acc.gcd(&n))
+}
+
+/// The lcm of all numbers, or 1 if there are none. None if it doesn't fit in T.
+pub fn lcm_all<T, I>(nrs: I) -> Option<T>
+where
+  T: Integer + CheckedMul + Copy,
+  I: IntoIterator<Item = T>,
+{
+  nrs.into_iter().try_fold(T::one(), |acc, n| {
+    if n.is_zero() || acc.is_zero() {
+      return Some(T::zero());
+    }
+    (acc / acc.gcd(&n)).checked_mul(&n)
+  })
+}
+
+/// Returns (g, x, y) such that a * x + b * y = g, where g is the gcd of a and b
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+  let (mut old_r, mut r) = (a, b);
+  let (mut old_x, mut x) = (1, 0);
+  let (mut old_y, mut y) = (0, 1);
+  while r != 0 {
+    let q = old_r / r;
+    (old_r, r) = (r, old_r - q * r);
+    (old_x, x) = (x, old_x - q * x);
+    (old_y, y) = (y, old_y - q * y);
+  }
+  if old_r < 0 {
+    (-old_r, -old_x, -old_y)
+  } else {
+    (old_r, old_x, old_y)
+  }
+}
+
+/// The x in 0..m for which a * x = 1 modulo m, if there is one. There is none
+/// if m isn't positive.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+  if m <= 0 {
+    return None;
+  }
+  let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+  (g == 1).then(|| x.rem_euclid(m))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+  NoSolution,
+  Overflow,
+  /// A modulus, or the period of a cycle, that isn't positive
+  NonPositiveModulus(i128),
+}
+
+impl fmt::Display for CrtError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      CrtError::NoSolution => write!(f, "The congruences have no common solution"),
+      CrtError::Overflow => write!(f, "The solution does not fit in an i128"),
+      CrtError::NonPositiveModulus(m) => write!(f, "The modulus {} is not positive", m),
+    }
+  }
+}
+
+/// Solves x = r (mod m) for every (r, m) pair. The moduli don't have to be
+/// coprime. Returns the smallest non-negative solution x and the modulus that
+/// all solutions repeat with, which is the lcm of the moduli.
+pub fn crt<I>(congruences: I) -> Result<(i128, i128), CrtError>
+where
+  I: IntoIterator<Item = (i128, i128)>,
+{
+  congruences
+    .into_iter()
+    .try_fold((0, 1), |(x, m), (r2, m2)| {
+      if m2 <= 0 {
+        return Err(CrtError::NonPositiveModulus(m2));
+      }
+      let r2 = r2.rem_euclid(m2);
+      let (g, p, _) = extended_gcd(m, m2);
+      if (r2 - x) % g != 0 {
+        return Err(CrtError::NoSolution);
+      }
+      let lcm = (m / g).checked_mul(m2).ok_or(CrtError::Overflow)?;
+      // x + m * k solves both, where k = (r2 - x) / g * p modulo m2 / g
+      let k = ((r2 - x) / g)
+        .checked_mul(p)
+        .ok_or(CrtError::Overflow)?
+        .rem_euclid(m2 / g);
+      let new_x = m
+        .checked_mul(k)
+        .and_then(|mk| mk.checked_add(x))
+        .ok_or(CrtError::Overflow)?;
+      Ok((new_x.rem_euclid(lcm), lcm))
+    })
+}
+
+/// The first time at which every cycle is at its event, where a cycle of
+/// (offset, period) has its event at offset, offset + period, offset + 2 * period,
+/// and so on.
+pub fn first_common_time<I>(cycles: I) -> Result<i128, CrtError>
+where
+  I: IntoIterator<Item = (i128, i128)>,
+{
+  let cycles: Vec<(i128, i128)> = cycles.into_iter().collect();
+  let (x, m) = crt(cycles.iter().copied())?;
+  // Before its offset a cycle hasn't had its first event yet
+  let earliest = cycles.iter().map(|(offset, _)| *offset).max().unwrap_or(0);
+  if x >= earliest {
+    Ok(x)
+  } else {
+    let periods = (earliest - x + m - 1) / m;
+    periods
+      .checked_mul(m)
+      .and_then(|pm| pm.checked_add(x))
+      .ok_or(CrtError::Overflow)
+  }
+}
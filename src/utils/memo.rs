@@ -0,0 +1,92 @@
+//! Caching the results of recursive functions.
+//!
+//! ```
+//! use aoc_template::utils::Memo;
+//!
+//! fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//!   if n < 2 {
+//!     return n;
+//!   }
+//!   memo.get_or_compute(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(fib(90, &mut memo), 2880067194370816120);
+//! assert!(memo.hits() > 0);
+//! ```
+
+use crate::utils::HashMap;
+use log::debug;
+use std::hash::Hash;
+
+/// A cache for a recursive function. Keys can borrow from the input, like a
+/// &str, or be indices into it, so looking something up doesn't allocate.
+/// How often the cache helped is logged at debug level (-vv) when it's dropped.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+  cache: HashMap<K, V>,
+  hits: usize,
+  misses: usize,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+  pub fn new() -> Self {
+    Memo {
+      cache: HashMap::default(),
+      hits: 0,
+      misses: 0,
+    }
+  }
+
+  /// The cached value for the key, or else the value computed by `compute`. That
+  /// gets the memo itself, so it can make its recursive calls through it.
+  pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+  where
+    F: FnOnce(&mut Self) -> V,
+  {
+    if let Some(v) = self.cache.get(&key) {
+      self.hits += 1;
+      return v.clone();
+    }
+    self.misses += 1;
+    let v = compute(self);
+    self.cache.insert(key, v.clone());
+    v
+  }
+
+  /// Forgets all cached values, for when the keys stop meaning the same thing,
+  /// like when moving on to the next line of the input. Keeps the statistics.
+  pub fn clear(&mut self) {
+    self.cache.clear();
+  }
+
+  pub fn len(&self) -> usize {
+    self.cache.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cache.is_empty()
+  }
+
+  pub fn hits(&self) -> usize {
+    self.hits
+  }
+
+  pub fn misses(&self) -> usize {
+    self.misses
+  }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+  fn default() -> Self {
+    Memo::new()
+  }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+  fn drop(&mut self) {
+    if self.hits + self.misses > 0 {
+      debug!("Memo had {} hits and {} misses", self.hits, self.misses);
+    }
+  }
+}
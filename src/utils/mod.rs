@@ -0,0 +1,84 @@
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+mod bits;
+mod cuboid;
+mod cycle;
+mod direction;
+mod graph;
+mod grid;
+mod interval;
+mod linalg;
+mod math;
+mod memo;
+mod ocr;
+mod parse;
+mod point;
+mod polygon;
+mod search;
+mod sequence;
+mod tiled;
+
+pub use bits::*;
+pub use cuboid::*;
+pub use cycle::*;
+pub use direction::*;
+pub use graph::*;
+pub use grid::*;
+pub use interval::*;
+pub use linalg::*;
+pub use math::*;
+pub use memo::*;
+pub use ocr::*;
+pub use parse::*;
+pub use point::*;
+pub use polygon::*;
+pub use search::*;
+pub use sequence::*;
+pub use tiled::*;
+
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+
+/// Makes all maps and sets that are created afterwards hash with the same fixed
+/// seed, so they iterate in the same order on every run.
+pub fn set_deterministic(deterministic: bool) {
+  DETERMINISTIC.store(deterministic, Ordering::Relaxed);
+}
+
+/// Hashes with a random seed like std's maps and sets do, or with a fixed seed in
+/// deterministic mode.
+#[derive(Debug, Clone)]
+pub struct HashState(Option<RandomState>);
+
+impl Default for HashState {
+  fn default() -> Self {
+    if DETERMINISTIC.load(Ordering::Relaxed) {
+      HashState(None)
+    } else {
+      HashState(Some(RandomState::new()))
+    }
+  }
+}
+
+impl BuildHasher for HashState {
+  type Hasher = DefaultHasher;
+
+  fn build_hasher(&self) -> DefaultHasher {
+    match &self.0 {
+      Some(random) => random.build_hasher(),
+      // DefaultHasher::new always uses the same keys
+      None => DefaultHasher::new(),
+    }
+  }
+}
+
+/// Use these instead of the ones in std::collections, so that --deterministic
+/// affects them. Create them with `default()` or `collect()` instead of `new()`.
+pub type HashMap<K, V> = std::collections::HashMap<K, V, HashState>;
+pub type HashSet<T> = std::collections::HashSet<T, HashState>;
+
+/// Positions on a grid, with y pointing down
+pub type Pos = Point2<i32>;
+/// For grids that are too large for i32 coordinates
+pub type Pos64 = Point2<i64>;
@@ -0,0 +1,155 @@
+//! Reading the block letters that some puzzles draw as their answer.
+//!
+//! ```
+//! use aoc_template::utils::read_drawing;
+//!
+//! let drawing = [
+//!   "#..#.###",
+//!   "#..#..#.",
+//!   "####..#.",
+//!   "#..#..#.",
+//!   "#..#..#.",
+//!   "#..#.###",
+//! ]
+//! .join("\n");
+//! assert_eq!(read_drawing(&drawing), Some("HI".to_owned()));
+//! ```
+
+use crate::utils::{Grid, Pos};
+
+// The letters Advent of Code draws with lit pixels, in the small alphabet that is
+// six pixels high, and the big alphabet that is ten pixels high. Not every letter
+// has ever shown up, so the alphabets are incomplete.
+const ALPHABET_6: [(char, [&str; 6]); 18] = [
+  ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+  ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+  ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+  ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+  ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+  ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+  ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+  ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+  ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+  ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+  ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+  ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+  ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+  ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+  ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+  ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+  ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const ALPHABET_10: [(char, [&str; 10]); 15] = [
+  ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+  ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+  ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+  ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+  ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+  ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+  ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+  ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+  ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+  ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+  ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+  ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+  ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+  ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+  ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Draws the lit pixels of a grid with '#' and the rest with '.', cropped to the
+/// smallest rectangle that contains every lit pixel.
+pub fn draw(grid: &Grid<bool>) -> String {
+  let lit: Vec<Pos> = grid.iter().filter(|(_, on)| **on).map(|(p, _)| p).collect();
+  if lit.is_empty() {
+    return String::new();
+  }
+  let minx = lit.iter().map(|p| p.x()).min().unwrap();
+  let maxx = lit.iter().map(|p| p.x()).max().unwrap();
+  let miny = lit.iter().map(|p| p.y()).min().unwrap();
+  let maxy = lit.iter().map(|p| p.y()).max().unwrap();
+  (miny..=maxy)
+    .map(|y| {
+      (minx..=maxx)
+        .map(|x| if grid[Pos::new(x, y)] { '#' } else { '.' })
+        .collect::<String>()
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+// Removes the empty columns on the left and right of a drawn letter, so letters
+// can be compared regardless of how they were spaced.
+fn trim_columns<S: AsRef<str>>(rows: &[S]) -> Vec<String> {
+  let width = rows.iter().map(|r| r.as_ref().len()).max().unwrap_or(0);
+  let used: Vec<usize> = (0..width)
+    .filter(|x| rows.iter().any(|r| r.as_ref().as_bytes().get(*x) == Some(&b'#')))
+    .collect();
+  match (used.first(), used.last()) {
+    (Some(&first), Some(&last)) => rows
+      .iter()
+      .map(|r| {
+        (first..=last)
+          .map(|x| if r.as_ref().as_bytes().get(x) == Some(&b'#') { '#' } else { '.' })
+          .collect()
+      })
+      .collect(),
+    _ => vec![],
+  }
+}
+
+fn recognise<const N: usize>(alphabet: &[(char, [&str; N])], letter: &[String]) -> Option<char> {
+  alphabet
+    .iter()
+    .find(|(_, glyph)| trim_columns(&glyph[..]) == letter)
+    .map(|(c, _)| *c)
+}
+
+/// Reads the block letters in a drawing made of '#' (lit) and any other character
+/// (unlit), like the output of `draw`. Letters are split on columns without any
+/// lit pixels. Returns None when the text is not 6 or 10 pixels high, or when any
+/// of the letters is not recognised.
+pub fn read_drawing(drawing: &str) -> Option<String> {
+  let is_lit = |r: &str, x: usize| r.as_bytes().get(x) == Some(&b'#');
+  let all_rows: Vec<&str> = drawing.lines().collect();
+  let first = all_rows.iter().position(|r| r.contains('#'))?;
+  let last = all_rows.iter().rposition(|r| r.contains('#'))?;
+  let rows = &all_rows[first..=last];
+  let width = rows.iter().map(|r| r.len()).max()?;
+  let is_empty_column = |x: usize| rows.iter().all(|r| !is_lit(r, x));
+
+  let mut letters: Vec<Vec<String>> = vec![];
+  let mut x = 0;
+  while x < width {
+    if is_empty_column(x) {
+      x += 1;
+      continue;
+    }
+    let start = x;
+    while x < width && !is_empty_column(x) {
+      x += 1;
+    }
+    letters.push(
+      rows
+        .iter()
+        .map(|r| (start..x).map(|x2| if is_lit(r, x2) { '#' } else { '.' }).collect())
+        .collect(),
+    );
+  }
+
+  letters
+    .iter()
+    .map(|letter| match rows.len() {
+      6 => recognise(&ALPHABET_6, letter),
+      10 => recognise(&ALPHABET_10, letter),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Reads the block letters drawn by the lit pixels of a grid.
+pub fn read_letters(grid: &Grid<bool>) -> Option<String> {
+  read_drawing(&draw(grid))
+}
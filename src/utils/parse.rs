@@ -0,0 +1,216 @@
+//! Parsers for the shapes that keep coming back in puzzle inputs. They all work
+//! on &str with nom's default error type, like the parsers of the days.
+//!
+//! ```
+//! use aoc_template::utils::{labelled, lines, spaced_numbers};
+//!
+//! let (_, times) = labelled("Time", spaced_numbers::<u32>)("Time:      7  15   30").unwrap();
+//! assert_eq!(times, vec![7, 15, 30]);
+//! let (_, rows) = lines(spaced_numbers::<i64>)("0 3 6\n-1 2\n").unwrap();
+//! assert_eq!(rows, vec![vec![0, 3, 6], vec![-1, 2]]);
+//! ```
+
+use crate::utils::{parse_grid, Grid};
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, newline, none_of, one_of, space0, space1};
+use nom::combinator::{all_consuming, map_opt, map_res, opt, recognize, value};
+use nom::error::Error;
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair, tuple};
+use nom::{IResult, Parser};
+use std::str::FromStr;
+
+/// An empty line between two blocks, which is two newlines in a row
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+  value((), pair(newline, newline))(input)
+}
+
+/// Blocks separated by blank lines, each parsed by the given parser
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  separated_list1(blank_line, block)
+}
+
+/// A number with an optional sign, of any type that can be parsed from a string.
+/// For unsigned types a minus sign is an error.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+  map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// One or more numbers with a separator in between, like ", "
+pub fn numbers<'a, T, S, O>(separator: S) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+  T: FromStr,
+  S: Parser<&'a str, O, Error<&'a str>>,
+{
+  separated_list1(separator, number)
+}
+
+/// One or more numbers separated by spaces, where the amount of spaces can
+/// differ, which is common when the numbers are lined up in columns
+pub fn spaced_numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+  numbers(space1)(input)
+}
+
+/// The value after a fixed label and a colon, like the numbers in
+/// "Time:   7  15  30". Any spaces after the colon are skipped.
+pub fn labelled<'a, O, F>(label: &'static str, value: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  preceded(tuple((tag(label), char(':'), space0)), value)
+}
+
+/// A key and a value separated by a colon, like "Game 3: ...". Any spaces after
+/// the colon are skipped.
+pub fn field<'a, K, V, FK, FV>(key: FK, value: FV) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+  FK: Parser<&'a str, K, Error<&'a str>>,
+  FV: Parser<&'a str, V, Error<&'a str>>,
+{
+  separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// Parses every line of the whole input with the given parser, which has to
+/// consume the entire line. A trailing newline is fine.
+pub fn lines<'a, O, F>(mut line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  move |input: &'a str| {
+    let mut res = vec![];
+    for l in input.lines() {
+      let (_, o) = all_consuming(|i| line.parse(i))(l)?;
+      res.push(o);
+    }
+    Ok(("", res))
+  }
+}
+
+/// A grid of which every cell is a single character, mapped to a cell by the
+/// given function. A row ends at the first character for which it returns None,
+/// so the grid fails to parse when that makes the rows differ in length.
+pub fn char_grid<'a, T, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+  F: FnMut(char) -> Option<T>,
+{
+  parse_grid(map_opt(none_of("\r\n"), cell))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::Pos;
+  use nom::character::complete::{alpha1, u32};
+  use nom::multi::separated_list0;
+
+  #[test]
+  fn blank_line_needs_two_newlines() {
+    assert_eq!(blank_line("\n\nrest"), Ok(("rest", ())));
+    assert!(blank_line("\nrest").is_err());
+  }
+
+  #[test]
+  fn blocks_are_split_on_blank_lines() {
+    let block = separated_list0(newline, alpha1);
+    assert_eq!(
+      blocks(block)("ab\ncd\n\nef\n\ngh"),
+      Ok(("", vec![vec!["ab", "cd"], vec!["ef"], vec!["gh"]]))
+    );
+  }
+
+  #[test]
+  fn blocks_leave_a_trailing_newline() {
+    let block = separated_list0(newline, alpha1);
+    assert_eq!(blocks(block)("ab\n\ncd\n"), Ok(("\n", vec![vec!["ab"], vec!["cd"]])));
+  }
+
+  #[test]
+  fn number_parses_signs() {
+    assert_eq!(number::<i64>("-12 x"), Ok((" x", -12)));
+    assert_eq!(number::<i64>("+12"), Ok(("", 12)));
+    assert_eq!(number::<u8>("255"), Ok(("", 255)));
+  }
+
+  #[test]
+  fn number_rejects_what_does_not_fit() {
+    assert!(number::<u32>("-1").is_err());
+    assert!(number::<u8>("256").is_err());
+    assert!(number::<i32>("abc").is_err());
+  }
+
+  #[test]
+  fn numbers_use_any_separator() {
+    assert_eq!(numbers::<i32, _, _>(tag(", "))("1, -2, 3"), Ok(("", vec![1, -2, 3])));
+    assert_eq!(numbers::<u64, _, _>(char(','))("4,5;6"), Ok((";6", vec![4, 5])));
+  }
+
+  #[test]
+  fn numbers_stop_before_a_trailing_separator() {
+    assert_eq!(numbers::<u32, _, _>(char(','))("1,2,"), Ok((",", vec![1, 2])));
+  }
+
+  #[test]
+  fn spaced_numbers_allow_runs_of_spaces() {
+    assert_eq!(spaced_numbers::<u32>("7  15   30 |"), Ok((" |", vec![7, 15, 30])));
+  }
+
+  #[test]
+  fn labelled_skips_the_label() {
+    assert_eq!(
+      labelled("Time", spaced_numbers::<u32>)("Time:      7  15"),
+      Ok(("", vec![7, 15]))
+    );
+    assert!(labelled("Time", spaced_numbers::<u32>)("Distance: 9").is_err());
+  }
+
+  #[test]
+  fn field_returns_key_and_value() {
+    let mut game = field(preceded(tag("Game "), u32), alpha1);
+    assert_eq!(game("Game 12: blue"), Ok(("", (12, "blue"))));
+  }
+
+  #[test]
+  fn lines_parse_the_whole_input() {
+    assert_eq!(lines(spaced_numbers::<i32>)("1 2\n-3\n"), Ok(("", vec![vec![1, 2], vec![-3]])));
+    assert_eq!(lines(alpha1)(""), Ok(("", vec![])));
+  }
+
+  #[test]
+  fn lines_must_be_consumed_entirely() {
+    assert!(lines(alpha1)("ab\ncd3\n").is_err());
+  }
+
+  #[test]
+  fn char_grid_maps_characters() {
+    let (rest, grid) = char_grid(|c| match c {
+      '#' => Some(true),
+      '.' => Some(false),
+      _ => None,
+    })("#.\n.#\n")
+    .unwrap();
+    assert_eq!(rest, "\n");
+    assert_eq!(grid.width(), 2);
+    assert_eq!(grid.height(), 2);
+    assert!(grid[Pos::new(1, 1)]);
+    assert!(!grid[Pos::new(1, 0)]);
+  }
+
+  #[test]
+  fn char_grid_stops_at_unknown_characters() {
+    let (rest, grid) = char_grid(|c| (c == '#').then_some(()))("##x").unwrap();
+    assert_eq!(rest, "x");
+    assert_eq!(grid.width(), 2);
+    assert!(char_grid(|c| (c == '#').then_some(()))("##\n#x").is_err());
+  }
+
+  #[test]
+  fn parsers_combine_into_input_parsers() {
+    let mut game = field(alpha1, separated_list1(newline, number::<i64>));
+    assert_eq!(game("draws: 1\n-2"), Ok(("", ("draws", vec![1, -2]))));
+    let (_, groups) = blocks(separated_list1(newline, u32))("1\n2\n\n3").unwrap();
+    assert_eq!(groups, vec![vec![1, 2], vec![3]]);
+  }
+}
@@ -0,0 +1,234 @@
+//! Points with any number of coordinates.
+//!
+//! ```
+//! use aoc_template::utils::{Point2, Point3};
+//!
+//! assert_eq!(Point2::new(1, 6).manhattan(Point2::new(5, 11)), 9);
+//! assert_eq!(Point3::new(1, 2, 3) + Point3::new(1, 1, 1), Point3::new(2, 3, 4));
+//! ```
+
+use num::Zero;
+use std::array;
+use std::fmt;
+use std::ops;
+
+/// A point with N integer coordinates. Comparing points compares their
+/// coordinates in order, so sorting them sorts by x first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+pub type Point2<T> = Point<T, 2>;
+pub type Point3<T> = Point<T, 3>;
+
+impl<T> Point<T, 2> {
+  pub fn new(x: T, y: T) -> Self {
+    Point([x, y])
+  }
+}
+
+impl<T: Copy> Point<T, 2> {
+  pub fn x(&self) -> T {
+    self.0[0]
+  }
+
+  pub fn y(&self) -> T {
+    self.0[1]
+  }
+}
+
+impl<T> Point<T, 3> {
+  pub fn new(x: T, y: T, z: T) -> Self {
+    Point([x, y, z])
+  }
+}
+
+impl<T: Copy> Point<T, 3> {
+  pub fn x(&self) -> T {
+    self.0[0]
+  }
+
+  pub fn y(&self) -> T {
+    self.0[1]
+  }
+
+  pub fn z(&self) -> T {
+    self.0[2]
+  }
+}
+
+impl<T: Copy, const N: usize> Point<T, N> {
+  pub fn origin() -> Self
+  where
+    T: Zero,
+  {
+    Point([T::zero(); N])
+  }
+
+  fn zip_with<F>(self, other: Self, mut f: F) -> Self
+  where
+    F: FnMut(T, T) -> T,
+  {
+    Point(array::from_fn(|i| f(self.0[i], other.0[i])))
+  }
+
+  pub fn min(self, other: Self) -> Self
+  where
+    T: Ord,
+  {
+    self.zip_with(other, Ord::min)
+  }
+
+  pub fn max(self, other: Self) -> Self
+  where
+    T: Ord,
+  {
+    self.zip_with(other, Ord::max)
+  }
+
+  // The distance along each axis. Works for unsigned coordinates too.
+  fn abs_diffs(self, other: Self) -> impl Iterator<Item = T>
+  where
+    T: Ord + ops::Sub<Output = T>,
+  {
+    (0..N).map(move |i| {
+      let (a, b) = (self.0[i], other.0[i]);
+      if a > b {
+        a - b
+      } else {
+        b - a
+      }
+    })
+  }
+
+  /// The number of steps between two points when moving along one axis at a time
+  pub fn manhattan(self, other: Self) -> T
+  where
+    T: Ord + Zero + ops::Sub<Output = T>,
+  {
+    self.abs_diffs(other).fold(T::zero(), |acc, d| acc + d)
+  }
+
+  /// The number of steps between two points when diagonal steps are allowed too
+  pub fn chebyshev(self, other: Self) -> T
+  where
+    T: Ord + Zero + ops::Sub<Output = T>,
+  {
+    self.abs_diffs(other).fold(T::zero(), Ord::max)
+  }
+
+  /// Whether the point lies in the box between two corners, inclusive
+  pub fn within(self, min: Self, max: Self) -> bool
+  where
+    T: Ord,
+  {
+    (0..N).all(|i| min.0[i] <= self.0[i] && self.0[i] <= max.0[i])
+  }
+
+  /// The smallest and largest corner of the box that contains all the points, or
+  /// None if there are no points.
+  pub fn bounding_box<I>(points: I) -> Option<(Self, Self)>
+  where
+    I: IntoIterator<Item = Self>,
+    T: Ord,
+  {
+    points.into_iter().fold(None, |bbox, p| match bbox {
+      None => Some((p, p)),
+      Some((min, max)) => Some((min.min(p), max.max(p))),
+    })
+  }
+}
+
+impl<T: Copy + ops::Add<Output = T>, const N: usize> ops::Add for Point<T, N> {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self {
+    self.zip_with(rhs, |a, b| a + b)
+  }
+}
+
+impl<T: Copy + ops::Sub<Output = T>, const N: usize> ops::Sub for Point<T, N> {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self {
+    self.zip_with(rhs, |a, b| a - b)
+  }
+}
+
+impl<T: Copy + ops::Add<Output = T>, const N: usize> ops::AddAssign for Point<T, N> {
+  fn add_assign(&mut self, rhs: Self) {
+    *self = *self + rhs;
+  }
+}
+
+impl<T: Copy + ops::Sub<Output = T>, const N: usize> ops::SubAssign for Point<T, N> {
+  fn sub_assign(&mut self, rhs: Self) {
+    *self = *self - rhs;
+  }
+}
+
+impl<T: Copy + ops::Neg<Output = T>, const N: usize> ops::Neg for Point<T, N> {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Point(self.0.map(|a| -a))
+  }
+}
+
+// Scaling by a number
+impl<T: Copy + ops::Mul<Output = T>, const N: usize> ops::Mul<T> for Point<T, N> {
+  type Output = Self;
+
+  fn mul(self, rhs: T) -> Self {
+    Point(self.0.map(|a| a * rhs))
+  }
+}
+
+// Conversions from and to the other ways positions are stored in the days
+
+impl<T, const N: usize> From<[T; N]> for Point<T, N> {
+  fn from(coords: [T; N]) -> Self {
+    Point(coords)
+  }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+  fn from((x, y): (T, T)) -> Self {
+    Point([x, y])
+  }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+  fn from(Point([x, y]): Point2<T>) -> Self {
+    (x, y)
+  }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+  fn from((x, y, z): (T, T, T)) -> Self {
+    Point([x, y, z])
+  }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+  fn from(Point([x, y, z]): Point3<T>) -> Self {
+    (x, y, z)
+  }
+}
+
+impl From<Point2<i32>> for Point2<i64> {
+  fn from(Point([x, y]): Point2<i32>) -> Self {
+    Point([x as i64, y as i64])
+  }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for Point<T, N> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, a) in self.0.iter().enumerate() {
+      if i > 0 {
+        write!(f, ",")?;
+      }
+      write!(f, "{}", a)?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,53 @@
+//! Helpers for polygons of which the corners are lattice points, given as the
+//! list of corners in order. The last corner connects back to the first one.
+//! Everything is computed and returned as i128, so even corners far apart can't
+//! overflow.
+//!
+//! ```
+//! use aoc_template::utils::{covered_cells, interior_points, Pos};
+//!
+//! // A square of 3 by 3 cells
+//! let corners = [Pos::new(0, 0), Pos::new(2, 0), Pos::new(2, 2), Pos::new(0, 2)];
+//! assert_eq!(covered_cells(&corners), 9);
+//! assert_eq!(interior_points(&corners), 1);
+//! ```
+
+use crate::utils::{Pos64, Rational};
+use num::BigInt;
+use num::integer::gcd;
+
+fn edges<P: Into<Pos64> + Copy>(vertices: &[P]) -> impl Iterator<Item = (Pos64, Pos64)> + '_ {
+  let n = vertices.len();
+  (0..n).map(move |i| (vertices[i].into(), vertices[(i + 1) % n].into()))
+}
+
+/// Twice the area, so that it is always a whole number. Positive when the corners
+/// go clockwise with y pointing down, like in the puzzle inputs.
+pub fn twice_signed_area<P: Into<Pos64> + Copy>(vertices: &[P]) -> i128 {
+  edges(vertices)
+    .map(|(a, b)| a.x() as i128 * b.y() as i128 - b.x() as i128 * a.y() as i128)
+    .sum()
+}
+
+/// The exact area, which can end in a half. Signed like `twice_signed_area`.
+pub fn signed_area<P: Into<Pos64> + Copy>(vertices: &[P]) -> Rational {
+  Rational::new(BigInt::from(twice_signed_area(vertices)), BigInt::from(2))
+}
+
+/// The number of lattice points on the edges, corners included
+pub fn boundary_points<P: Into<Pos64> + Copy>(vertices: &[P]) -> i128 {
+  edges(vertices)
+    .map(|(a, b)| gcd(b.x() as i128 - a.x() as i128, b.y() as i128 - a.y() as i128))
+    .sum()
+}
+
+/// The number of lattice points strictly inside, using Pick's theorem
+pub fn interior_points<P: Into<Pos64> + Copy>(vertices: &[P]) -> i128 {
+  (twice_signed_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// The number of grid cells that are inside or on the edge, when every lattice
+/// point stands for a cell
+pub fn covered_cells<P: Into<Pos64> + Copy>(vertices: &[P]) -> i128 {
+  interior_points(vertices) + boundary_points(vertices)
+}
@@ -0,0 +1,324 @@
+//! Searches over nodes of any type, given a function for the successors of a
+//! node. Every search can stop early at the first node that satisfies `is_goal`;
+//! pass `|_| false` to search everything that can be reached.
+//!
+//! ```
+//! use aoc_template::utils::Search;
+//!
+//! // From 0 to 10 with steps of 1 that cost 1 and steps of 3 that cost 2
+//! let res = Search::with_paths().dijkstra(0, |n: &u32| [(n + 1, 1), (n + 3, 2)], |n| *n == 10);
+//! assert_eq!(res.goal_cost(), Some(7));
+//! assert_eq!(res.goal_path().unwrap().len(), 5);
+//! ```
+
+use crate::utils::HashMap;
+use log::debug;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+/// How much work a search did. Logged at debug level (-vv) after every search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+  /// Nodes of which the successors were generated
+  pub expanded: usize,
+  /// Nodes that were put on the queue, including the starts
+  pub pushed: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult<N> {
+  /// The cost of the cheapest path found to every node that was reached
+  pub costs: HashMap<N, usize>,
+  /// The goal the search stopped at, if any
+  pub goal: Option<N>,
+  pub stats: SearchStats,
+  // Only filled in when the search tracks paths
+  parents: Option<HashMap<N, N>>,
+}
+
+impl<N: Clone + Eq + Hash> SearchResult<N> {
+  pub fn cost(&self, node: &N) -> Option<usize> {
+    self.costs.get(node).copied()
+  }
+
+  pub fn goal_cost(&self) -> Option<usize> {
+    self.goal.as_ref().and_then(|g| self.cost(g))
+  }
+
+  /// The cheapest path from a start to the node, both included. None if the node
+  /// wasn't reached, or if the search didn't track paths.
+  pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+    let parents = self.parents.as_ref()?;
+    self.costs.get(node)?;
+    let mut path = vec![node.clone()];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+      path.push(parent.clone());
+    }
+    path.reverse();
+    Some(path)
+  }
+
+  pub fn goal_path(&self) -> Option<Vec<N>> {
+    self.goal.as_ref().and_then(|g| self.path_to(g))
+  }
+}
+
+/// A priority queue for small integer priorities, with a list of items for every
+/// priority. Pushing and popping are constant time, as long as the priorities
+/// don't get much larger than the number of items.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+  buckets: Vec<Vec<T>>,
+  // No bucket before this one has items
+  first: usize,
+  len: usize,
+}
+
+impl<T> BucketQueue<T> {
+  pub fn new() -> Self {
+    BucketQueue {
+      buckets: vec![],
+      first: 0,
+      len: 0,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn push(&mut self, priority: usize, item: T) {
+    if priority >= self.buckets.len() {
+      self.buckets.resize_with(priority + 1, Vec::new);
+    }
+    self.buckets[priority].push(item);
+    self.first = self.first.min(priority);
+    self.len += 1;
+  }
+
+  /// An item with the lowest priority, and that priority
+  pub fn pop(&mut self) -> Option<(usize, T)> {
+    while self.first < self.buckets.len() {
+      if let Some(item) = self.buckets[self.first].pop() {
+        self.len -= 1;
+        return Some((self.first, item));
+      }
+      self.first += 1;
+    }
+    None
+  }
+}
+
+impl<T> Default for BucketQueue<T> {
+  fn default() -> Self {
+    BucketQueue::new()
+  }
+}
+
+/// Settings shared by all searches. Use `Search::default()`, or the functions
+/// below it when the defaults are fine.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Search {
+  /// Remember how every node was reached, so `path_to` works
+  pub track_paths: bool,
+  /// Don't go further than this cost. Nodes at exactly this cost are reached,
+  /// but not expanded.
+  pub max_cost: Option<usize>,
+}
+
+impl Search {
+  pub fn with_paths() -> Self {
+    Search {
+      track_paths: true,
+      ..Search::default()
+    }
+  }
+
+  pub fn with_max_cost(max_cost: usize) -> Self {
+    Search {
+      max_cost: Some(max_cost),
+      ..Search::default()
+    }
+  }
+
+  fn within_max_cost(&self, cost: usize) -> bool {
+    self.max_cost.is_none_or(|max| cost <= max)
+  }
+
+  fn new_result<N: Clone + Eq + Hash>(&self) -> SearchResult<N> {
+    SearchResult {
+      costs: HashMap::default(),
+      goal: None,
+      stats: SearchStats::default(),
+      parents: self.track_paths.then(HashMap::default),
+    }
+  }
+
+  fn log_stats(&self, kind: &str, stats: SearchStats) {
+    debug!(
+      "{} expanded {} nodes and pushed {}",
+      kind, stats.expanded, stats.pushed
+    );
+  }
+
+  /// Breadth first search from all starts at once. The cost of a node is the
+  /// number of steps from the nearest start.
+  pub fn multi_bfs<N, S, FN, IN, FG>(&self, starts: S, mut successors: FN, mut is_goal: FG) -> SearchResult<N>
+  where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+  {
+    let mut res = self.new_result();
+    let mut q = VecDeque::new();
+    for start in starts {
+      if !res.costs.contains_key(&start) {
+        res.costs.insert(start.clone(), 0);
+        q.push_back((start, 0));
+        res.stats.pushed += 1;
+      }
+    }
+    while let Some((node, cost)) = q.pop_front() {
+      res.stats.expanded += 1;
+      if is_goal(&node) {
+        res.goal = Some(node);
+        break;
+      }
+      if !self.within_max_cost(cost + 1) {
+        continue;
+      }
+      for next in successors(&node) {
+        if !res.costs.contains_key(&next) {
+          res.costs.insert(next.clone(), cost + 1);
+          if let Some(parents) = res.parents.as_mut() {
+            parents.insert(next.clone(), node.clone());
+          }
+          q.push_back((next, cost + 1));
+          res.stats.pushed += 1;
+        }
+      }
+    }
+    self.log_stats("BFS", res.stats);
+    res
+  }
+
+  pub fn bfs<N, FN, IN, FG>(&self, start: N, successors: FN, is_goal: FG) -> SearchResult<N>
+  where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+  {
+    self.multi_bfs([start], successors, is_goal)
+  }
+
+  /// A* with a bucket queue, so the costs have to be small integers. The
+  /// heuristic must never overestimate the cost to the goal. If it's also
+  /// consistent, no node is expanded twice.
+  pub fn astar<N, FN, IN, FH, FG>(
+    &self,
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+  ) -> SearchResult<N>
+  where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, usize)>,
+    FH: FnMut(&N) -> usize,
+    FG: FnMut(&N) -> bool,
+  {
+    let mut res = self.new_result();
+    let mut q = BucketQueue::new();
+    res.costs.insert(start.clone(), 0);
+    q.push(heuristic(&start), (start, 0));
+    res.stats.pushed += 1;
+    while let Some((_, (node, cost))) = q.pop() {
+      // Skip nodes that were pushed again with a lower cost after this
+      if res.costs[&node] < cost {
+        continue;
+      }
+      res.stats.expanded += 1;
+      if is_goal(&node) {
+        res.goal = Some(node);
+        break;
+      }
+      for (next, step_cost) in successors(&node) {
+        let next_cost = cost + step_cost;
+        if !self.within_max_cost(next_cost) {
+          continue;
+        }
+        if res.costs.get(&next).is_none_or(|c| next_cost < *c) {
+          res.costs.insert(next.clone(), next_cost);
+          if let Some(parents) = res.parents.as_mut() {
+            parents.insert(next.clone(), node.clone());
+          }
+          q.push(next_cost + heuristic(&next), (next, next_cost));
+          res.stats.pushed += 1;
+        }
+      }
+    }
+    self.log_stats("A*", res.stats);
+    res
+  }
+
+  /// Dijkstra with a bucket queue, so the costs have to be small integers
+  pub fn dijkstra<N, FN, IN, FG>(&self, start: N, successors: FN, is_goal: FG) -> SearchResult<N>
+  where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, usize)>,
+    FG: FnMut(&N) -> bool,
+  {
+    self.astar(start, successors, |_| 0, is_goal)
+  }
+}
+
+pub fn bfs<N, FN, IN, FG>(start: N, successors: FN, is_goal: FG) -> SearchResult<N>
+where
+  N: Clone + Eq + Hash,
+  FN: FnMut(&N) -> IN,
+  IN: IntoIterator<Item = N>,
+  FG: FnMut(&N) -> bool,
+{
+  Search::default().bfs(start, successors, is_goal)
+}
+
+pub fn multi_bfs<N, S, FN, IN, FG>(starts: S, successors: FN, is_goal: FG) -> SearchResult<N>
+where
+  N: Clone + Eq + Hash,
+  S: IntoIterator<Item = N>,
+  FN: FnMut(&N) -> IN,
+  IN: IntoIterator<Item = N>,
+  FG: FnMut(&N) -> bool,
+{
+  Search::default().multi_bfs(starts, successors, is_goal)
+}
+
+pub fn dijkstra<N, FN, IN, FG>(start: N, successors: FN, is_goal: FG) -> SearchResult<N>
+where
+  N: Clone + Eq + Hash,
+  FN: FnMut(&N) -> IN,
+  IN: IntoIterator<Item = (N, usize)>,
+  FG: FnMut(&N) -> bool,
+{
+  Search::default().dijkstra(start, successors, is_goal)
+}
+
+pub fn astar<N, FN, IN, FH, FG>(start: N, successors: FN, heuristic: FH, is_goal: FG) -> SearchResult<N>
+where
+  N: Clone + Eq + Hash,
+  FN: FnMut(&N) -> IN,
+  IN: IntoIterator<Item = (N, usize)>,
+  FH: FnMut(&N) -> usize,
+  FG: FnMut(&N) -> bool,
+{
+  Search::default().astar(start, successors, heuristic, is_goal)
+}
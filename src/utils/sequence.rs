@@ -0,0 +1,157 @@
+//! Extrapolating integer sequences and fitting polynomials through points.
+//!
+//! ```
+//! use aoc_template::utils::{Polynomial, Sequence};
+//!
+//! let triangular = Sequence::from_samples(&[1, 3, 6, 10, 15]).unwrap();
+//! assert_eq!(triangular.degree(), 2);
+//! assert_eq!(triangular.at(5), 21.into());
+//! assert_eq!(triangular.at(-1), 0.into());
+//!
+//! // x^2 + x + 1
+//! let p = Polynomial::fit(&[(0, 1), (1, 3), (2, 7)]).unwrap();
+//! assert_eq!(p.eval_int(3), Some(13.into()));
+//! ```
+
+use num::{BigInt, BigRational, One, Zero};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceError {
+  NoSamples,
+  DuplicateX,
+}
+
+impl fmt::Display for SequenceError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SequenceError::NoSamples => write!(f, "There are no samples"),
+      SequenceError::DuplicateX => write!(f, "Two samples have the same x"),
+    }
+  }
+}
+
+/// An integer sequence that follows a polynomial, found from consecutive samples
+/// by taking differences until they are constant. A row of a single value counts
+/// as constant, so any samples give a sequence, of at most one degree less than
+/// there are samples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+  // The first value of the samples and of every row of differences after them,
+  // up to the constant row
+  leading: Vec<BigInt>,
+}
+
+impl Sequence {
+  pub fn from_samples<T: Into<BigInt> + Copy>(samples: &[T]) -> Result<Sequence, SequenceError> {
+    if samples.is_empty() {
+      return Err(SequenceError::NoSamples);
+    }
+    let mut row: Vec<BigInt> = samples.iter().map(|s| (*s).into()).collect();
+    let mut leading = vec![];
+    loop {
+      leading.push(row[0].clone());
+      if row.iter().all(|v| *v == row[0]) {
+        return Ok(Sequence { leading });
+      }
+      row = row.windows(2).map(|w| &w[1] - &w[0]).collect();
+    }
+  }
+
+  pub fn degree(&self) -> usize {
+    self.leading.len() - 1
+  }
+
+  /// The value at index n, where the first sample is at 0. Works for any n, also
+  /// before the first sample.
+  pub fn at(&self, n: i64) -> BigInt {
+    // Newton's forward difference formula: the sum of the k-th leading difference
+    // times n choose k
+    let n = BigInt::from(n);
+    let mut choose = BigInt::one();
+    let mut res = BigInt::zero();
+    for (k, d) in self.leading.iter().enumerate() {
+      res += d * &choose;
+      choose = choose * (&n - k) / (k + 1);
+    }
+    res
+  }
+}
+
+/// A polynomial with exact rational coefficients, lowest power first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+  coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+  /// The polynomial of the lowest degree that goes through all the points, using
+  /// Lagrange interpolation
+  pub fn fit<T: Into<BigInt> + Copy>(points: &[(T, T)]) -> Result<Polynomial, SequenceError> {
+    if points.is_empty() {
+      return Err(SequenceError::NoSamples);
+    }
+    let points: Vec<(BigRational, BigRational)> = points
+      .iter()
+      .map(|(x, y)| (BigRational::from((*x).into()), BigRational::from((*y).into())))
+      .collect();
+    let mut coefficients = vec![BigRational::zero(); points.len()];
+    for (i, (xi, yi)) in points.iter().enumerate() {
+      // The basis polynomial that is 1 at xi and 0 at the other xs
+      let mut basis = vec![BigRational::one()];
+      let mut denominator = BigRational::one();
+      for (j, (xj, _)) in points.iter().enumerate() {
+        if i == j {
+          continue;
+        }
+        if xi == xj {
+          return Err(SequenceError::DuplicateX);
+        }
+        // Multiply by (x - xj)
+        let mut next = vec![BigRational::zero(); basis.len() + 1];
+        for (k, c) in basis.iter().enumerate() {
+          next[k + 1] += c;
+          next[k] -= c * xj;
+        }
+        basis = next;
+        denominator *= xi - xj;
+      }
+      let scale = yi / denominator;
+      for (k, c) in basis.iter().enumerate() {
+        coefficients[k] += c * &scale;
+      }
+    }
+    let mut res = Polynomial { coefficients };
+    res.trim();
+    Ok(res)
+  }
+
+  fn trim(&mut self) {
+    while self.coefficients.len() > 1 && self.coefficients.last().unwrap().is_zero() {
+      self.coefficients.pop();
+    }
+  }
+
+  pub fn coefficients(&self) -> &[BigRational] {
+    &self.coefficients
+  }
+
+  pub fn degree(&self) -> usize {
+    self.coefficients.len() - 1
+  }
+
+  pub fn eval(&self, x: &BigRational) -> BigRational {
+    // Horner's method
+    self
+      .coefficients
+      .iter()
+      .rev()
+      .fold(BigRational::zero(), |acc, c| acc * x + c)
+  }
+
+  /// The value at an integer x, or None if that isn't an integer
+  pub fn eval_int<T: Into<BigInt>>(&self, x: T) -> Option<BigInt> {
+    let y = self.eval(&BigRational::from(x.into()));
+    y.is_integer().then(|| y.to_integer())
+  }
+}
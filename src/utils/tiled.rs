@@ -0,0 +1,81 @@
+//! Grids that repeat infinitely in every direction.
+//!
+//! ```
+//! use aoc_template::utils::{Grid, Pos};
+//!
+//! let grid = Grid::from_rows(vec![vec!['.', '#'], vec!['.', '.']]);
+//! let garden = grid.tiled();
+//! assert_eq!(garden[Pos::new(3, -2)], '#');
+//! assert_eq!(garden.tile_of(Pos::new(3, -2)), Pos::new(1, -1));
+//! ```
+
+use crate::utils::{Direction, Grid, HashMap, Pos, Search};
+use std::ops::Index;
+
+/// A finite grid repeated infinitely in every direction. Positions are in the
+/// whole plane, and the grid itself is the tile at (0, 0).
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+  grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+  pub fn tiled(&self) -> Tiled<'_, T> {
+    Tiled { grid: self }
+  }
+}
+
+impl<'a, T> Tiled<'a, T> {
+  pub fn tile_width(&self) -> i32 {
+    self.grid.width() as i32
+  }
+
+  pub fn tile_height(&self) -> i32 {
+    self.grid.height() as i32
+  }
+
+  /// Which copy of the grid a position is in
+  pub fn tile_of(&self, pos: Pos) -> Pos {
+    Pos::new(pos.x().div_euclid(self.tile_width()), pos.y().div_euclid(self.tile_height()))
+  }
+
+  /// The position in the grid itself that a position is a copy of
+  pub fn local(&self, pos: Pos) -> Pos {
+    Pos::new(pos.x().rem_euclid(self.tile_width()), pos.y().rem_euclid(self.tile_height()))
+  }
+
+  pub fn get(&self, pos: Pos) -> &'a T {
+    &self.grid[self.local(pos)]
+  }
+
+  /// There is no edge, so every position has all four neighbours
+  pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+    Direction::cardinals().map(move |d| pos + d.to_pos())
+  }
+
+  /// The fewest steps to every position that can be reached from `start` in at
+  /// most `max_steps` steps, only stepping onto cells for which `passable` holds.
+  pub fn bfs<F>(&self, start: Pos, max_steps: usize, passable: F) -> HashMap<Pos, usize>
+  where
+    F: Fn(&T) -> bool,
+  {
+    let tiled = self;
+    let passable = &passable;
+    let successors = move |pos: &Pos| {
+      tiled
+        .neighbours_4(*pos)
+        .filter(move |next| passable(tiled.get(*next)))
+    };
+    Search::with_max_cost(max_steps)
+      .bfs(start, successors, |_| false)
+      .costs
+  }
+}
+
+impl<'a, T> Index<Pos> for Tiled<'a, T> {
+  type Output = T;
+
+  fn index(&self, pos: Pos) -> &T {
+    self.get(pos)
+  }
+}
@@ -0,0 +1,66 @@
+use aoc_template::utils::{BitGrid, BitSet, DirLayers, Direction, Grid};
+
+mod common;
+
+use common::pos;
+
+#[test]
+fn bit_set_operations() {
+  let a: BitSet = [1, 5, 64, 99].into_iter().fold(BitSet::new(100), |mut s, i| {
+    s.insert(i);
+    s
+  });
+  let mut b = BitSet::new(100);
+  b.insert(5);
+  b.insert(70);
+  assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![1, 5, 64, 70, 99]);
+  assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![5]);
+  assert!(!a.contains(100));
+}
+
+#[test]
+fn bit_grid_counts_positions_over_layers() {
+  let mut seen = BitGrid::with_dir_layers(3, 3, DirLayers::All);
+  seen.insert_dir(pos(1, 1), Direction::N);
+  seen.insert_dir(pos(1, 1), Direction::S);
+  seen.insert_dir(pos(2, 0), Direction::E);
+  assert_eq!(seen.count(), 3);
+  assert_eq!(seen.count_positions(), 2);
+}
+
+#[test]
+fn bit_grids_can_have_a_layer_per_cardinal() {
+  let grid = Grid::new(3, 3, ());
+  let mut seen = BitGrid::for_grid_with_cardinals(&grid);
+  assert!(seen.insert_dir(pos(1, 1), Direction::W));
+  assert!(!seen.insert_dir(pos(1, 1), Direction::W));
+  assert!(!seen.contains_dir(pos(1, 1), Direction::S));
+  assert_eq!(seen.count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "has no layer")]
+fn bit_grids_of_cardinals_have_no_diagonals() {
+  let grid = Grid::new(3, 3, ());
+  BitGrid::for_grid_with_cardinals(&grid).insert_dir(pos(1, 1), Direction::NE);
+}
+
+#[test]
+#[should_panic(expected = "without direction layers")]
+fn bit_grids_with_numbered_layers_have_no_directions() {
+  BitGrid::with_layers(3, 3, 4).insert_dir(pos(1, 1), Direction::N);
+}
+
+#[test]
+#[should_panic(expected = "without direction layers")]
+fn bit_grids_of_one_layer_have_no_directions() {
+  BitGrid::new(3, 3).insert_dir(pos(1, 1), Direction::N);
+}
+
+#[test]
+#[should_panic(expected = "different sizes or layers")]
+fn bit_grids_only_combine_with_the_same_shape() {
+  // The same number of bits, but not the same positions
+  let mut a = BitGrid::new(2, 3);
+  a.union_with(&BitGrid::new(3, 2));
+}
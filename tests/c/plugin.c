@@ -0,0 +1,53 @@
+// A plugin for day 6 that doesn't solve it, but answers with the number of
+// lines and of bytes of the input, to test loading plugins. Define
+// PLUGIN_ABI_VERSION to build it for another version of the ABI.
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "aoc_template.h"
+
+#ifndef PLUGIN_ABI_VERSION
+#define PLUGIN_ABI_VERSION AOC_PLUGIN_ABI_VERSION
+#endif
+
+static int32_t answer_with(size_t n, char **answer) {
+  *answer = malloc(32);
+  if (*answer == NULL) {
+    return AOC_STATUS_PANICKED;
+  }
+  snprintf(*answer, 32, "%zu", n);
+  return AOC_STATUS_OK;
+}
+
+static int32_t count_lines(const uint8_t *input, size_t input_len, char **answer) {
+  size_t lines = 0;
+  for (size_t i = 0; i < input_len; i++) {
+    lines += input[i] == '\n';
+  }
+  return answer_with(lines, answer);
+}
+
+static int32_t count_bytes(const uint8_t *input, size_t input_len, char **answer) {
+  (void)input;
+  return answer_with(input_len, answer);
+}
+
+static void free_answer(char *answer) {
+  free(answer);
+}
+
+static const AocPluginDescriptor descriptor = {
+  .year = 2023,
+  .day = 6,
+  .part_1 = count_lines,
+  .part_2 = count_bytes,
+  .free_answer = free_answer,
+};
+
+uint32_t aoc_plugin_abi_version(void) {
+  return PLUGIN_ABI_VERSION;
+}
+
+const AocPluginDescriptor *aoc_plugin_descriptor(void) {
+  return &descriptor;
+}
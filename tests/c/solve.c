@@ -0,0 +1,46 @@
+// Solves the example of day 6 through the C interface, and checks the errors
+// for calls that can't be solved. Exits with 1 on the first failed check.
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc_template.h"
+
+static int failures = 0;
+
+static void check_answer(uint32_t day, uint32_t part, const char *input, const char *expected) {
+  char *answer = NULL;
+  AocStatus status = aoc_solve(2023, day, part, (const uint8_t *)input, strlen(input), &answer);
+  if (status != AOC_STATUS_OK || answer == NULL || strcmp(answer, expected) != 0) {
+    fprintf(stderr, "day %u part %u: expected %s, got status %d and answer %s\n", day, part, expected,
+            status, answer ? answer : "(null)");
+    failures++;
+  }
+  aoc_free_answer(answer);
+}
+
+static void check_status(uint32_t year, uint32_t day, uint32_t part, const char *input, AocStatus expected) {
+  char *answer = NULL;
+  size_t len = input ? strlen(input) : 0;
+  AocStatus status = aoc_solve(year, day, part, (const uint8_t *)input, len, &answer);
+  if (status != expected || answer != NULL) {
+    fprintf(stderr, "%u day %u part %u: expected status %d, got %d\n", year, day, part, expected, status);
+    failures++;
+  }
+}
+
+int main(void) {
+  const char *races = "Time:      7  15   30\nDistance:  9  40  200\n";
+  check_answer(6, 1, races, "288");
+  check_answer(6, 2, races, "71503");
+
+  check_status(2022, 6, 1, races, AOC_STATUS_UNKNOWN_YEAR);
+  check_status(2023, 26, 1, races, AOC_STATUS_UNKNOWN_DAY);
+  check_status(2023, 6, 3, races, AOC_STATUS_UNKNOWN_PART);
+  check_status(2023, 6, 1, NULL, AOC_STATUS_INVALID_INPUT);
+  check_status(2023, 6, 1, "\xff\xfe", AOC_STATUS_INVALID_INPUT);
+  check_status(2023, 6, 1, "Distance: 9\n", AOC_STATUS_PARSE_ERROR);
+
+  // Freeing null is allowed
+  aoc_free_answer(NULL);
+  return failures == 0 ? 0 : 1;
+}
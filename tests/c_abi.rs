@@ -0,0 +1,42 @@
+mod common;
+
+// Compiles tests/c/solve.c against the cdylib and the generated header, and
+// runs it. Linking with an rpath only works like this on unix.
+#[cfg(unix)]
+#[test]
+fn c_program_links_and_solves() {
+  use std::path::PathBuf;
+  use std::process::Command;
+
+  let lib_dir = common::lib_dir();
+  let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("c_solve");
+  common::compile_c(
+    "solve.c",
+    &out,
+    &[
+      "-L",
+      &lib_dir.display().to_string(),
+      &format!("-Wl,-rpath,{}", lib_dir.display()),
+      "-laoc_template",
+    ],
+  );
+
+  let output = Command::new(&out).output().expect("Could not run the C program");
+  assert!(
+    output.status.success(),
+    "The C program failed:\n{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+}
+
+// The header is only regenerated into OUT_DIR, so the checked-in one has to be
+// updated by hand, with `AOC_UPDATE_HEADER=1 cargo build`
+#[test]
+fn checked_in_header_is_up_to_date() {
+  let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc_template.h"));
+  let checked_in = include_str!("../include/aoc_template.h");
+  assert!(
+    generated == checked_in,
+    "include/aoc_template.h is out of date, run `AOC_UPDATE_HEADER=1 cargo build`"
+  );
+}
@@ -0,0 +1,52 @@
+// Not every test uses every helper
+#![allow(dead_code)]
+
+use aoc_template::utils::{char_grid, Grid, Pos};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// The directory with the libraries of this crate. Integration tests run from
+// target/<profile>/deps, next to it.
+pub fn lib_dir() -> PathBuf {
+  let exe = env::current_exe().unwrap();
+  exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+// Compiles a C file in tests/c with the generated header, and with `cc` or
+// whatever $CC is. Panics if it doesn't compile.
+pub fn compile_c(source: &str, out: &Path, args: &[&str]) {
+  let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+  let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+  let status = Command::new(compiler)
+    .arg(root.join("tests/c").join(source))
+    .arg("-I")
+    .arg(root.join("include"))
+    .args(["-Wall", "-Werror"])
+    .args(args)
+    .arg("-o")
+    .arg(out)
+    .status()
+    .expect("Could not start the C compiler");
+  assert!(status.success(), "Could not compile tests/c/{}", source);
+}
+
+pub fn pos(x: i32, y: i32) -> Pos {
+  Pos::new(x, y)
+}
+
+// A small maze of open (true) and walled (false) tiles:
+//
+// ...#
+// .#.#
+// .#..
+// ...#
+pub fn maze() -> Grid<bool> {
+  let (_, grid) = char_grid(|c| match c {
+    '#' => Some(false),
+    '.' => Some(true),
+    _ => None,
+  })("...#\n.#.#\n.#..\n...#")
+  .unwrap();
+  grid
+}
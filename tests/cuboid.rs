@@ -0,0 +1,20 @@
+use aoc_template::utils::{Cuboid, HeightMap, Point3};
+
+mod common;
+
+use common::pos;
+
+#[test]
+fn cuboids_stack_on_the_highest_box_below() {
+  let low = Cuboid::new(Point3::new(0, 0, 1), Point3::new(2, 0, 1));
+  let high = Cuboid::new(Point3::new(2, 0, 3), Point3::new(2, 2, 3));
+  let falling = Cuboid::new(Point3::new(0, 0, 9), Point3::new(2, 0, 9));
+  let mut stack = HeightMap::new();
+  stack.fall(&low, 0, 1);
+  stack.fall(&high, 1, 1);
+  let (landed, supports) = stack.fall(&falling, 2, 1);
+  assert_eq!(landed.bottom(), 3);
+  assert_eq!(supports, vec![1]);
+  assert_eq!(stack.height_at(pos(0, 0)), Some(3));
+  assert_eq!(low.intersection(&high), None);
+}
@@ -0,0 +1,33 @@
+use aoc_template::utils::{find_cycle, state_at};
+
+#[test]
+fn cycles_skip_ahead() {
+  let step = |s: &mut Vec<u8>| s.rotate_left(1);
+  let cycle = find_cycle(&vec![1, 2, 3], step);
+  assert_eq!((cycle.start, cycle.len), (0, 3));
+  assert_eq!(state_at(vec![1, 2, 3], 1_000_000_000_000, step), vec![2, 3, 1]);
+}
+
+#[test]
+fn cycles_with_a_tail_skip_ahead() {
+  // 0, 1, ..., 99, then 50, 51, ..., 99 over and over
+  let step = |n: &mut u32| *n = if *n == 99 { 50 } else { *n + 1 };
+  let cycle = find_cycle(&0, step);
+  assert_eq!((cycle.start, cycle.len), (50, 50));
+  for n in [0, 1, 49, 50, 99, 100, 149, 150, 1_000_000_007] {
+    let expected = if n < 100 { n as u32 } else { 50 + ((n - 50) % 50) as u32 };
+    assert_eq!(state_at(0, n, step), expected, "after {} steps", n);
+  }
+}
+
+#[test]
+fn state_at_stops_early() {
+  // These states never repeat, so finding the cycle would never end
+  let mut steps = 0;
+  let step = |n: &mut u64| {
+    steps += 1;
+    *n += 1;
+  };
+  assert_eq!(state_at(0, 3, step), 3);
+  assert_eq!(steps, 3);
+}
@@ -0,0 +1,149 @@
+use aoc_template::days::{day04::Day04, day06::Day06, day08::Day08, day09::Day09, day10::Day10, day14::Day14, day20::Day20, day24::Day24};
+use aoc_template::Day;
+use std::fmt::Display;
+
+// Solves both parts of a day for the example of the puzzle
+fn solve<D: Day>(input: &str) -> (String, String) {
+  let (_, input) = D::parse(input).expect("The example should parse");
+  let input = D::prepare(input).expect("The example should be solvable");
+  (D::part_1(&input).to_string(), D::part_2(&input).to_string())
+}
+
+fn answers<A: Display, B: Display>(a: A, b: B) -> (String, String) {
+  (a.to_string(), b.to_string())
+}
+
+#[test]
+fn day04_spacing_around_the_bar_can_vary() {
+  let input = "Card 1: 41 48 | 41 9\nCard  2:  1  2|3 1\n";
+  assert_eq!(solve::<Day04>(input), answers(2, 3));
+}
+
+#[test]
+fn day06_example() {
+  let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+  assert_eq!(solve::<Day06>(input), answers(288, 71503));
+}
+
+#[test]
+fn day08_ghosts_can_start_out_of_phase() {
+  // The first ghost gets to 11Z after 2 steps and then every 2 steps, the second
+  // one gets to 22Z after 2 steps and then every 4 steps. With only the loop
+  // lengths, it would look like they meet after 4 steps.
+  let input = "\
+L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22D, 22D)
+22D = (22E, 22E)
+22E = (22F, 22F)
+22F = (22Z, 22Z)
+";
+  let (_, input) = Day08::parse(input).unwrap();
+  let input = Day08::prepare(input).unwrap();
+  assert_eq!(Day08::part_2(&input), 2);
+}
+
+#[test]
+fn day08_only_takes_left_and_right() {
+  assert!(Day08::parse("LRL\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").is_ok());
+  assert!(Day08::parse("LE\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").is_err());
+  assert!(Day08::parse("R>\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").is_err());
+}
+
+#[test]
+fn day09_example() {
+  let input = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+  assert_eq!(solve::<Day09>(input), answers(114, 2));
+}
+
+#[test]
+fn day09_rows_can_be_short_or_large() {
+  let input = "5\n2000000000 2050000000 2100000000\n";
+  assert_eq!(solve::<Day09>(input), answers(2150000005u64, 1950000005u64));
+  // 1 2 goes on to 3 and came from 0, and 1 2 4 goes on to 7 and came from 1
+  assert_eq!(solve::<Day09>("1 2\n1 2 4\n"), answers(10, 1));
+}
+
+#[test]
+fn day09_rejects_empty_rows() {
+  assert!(Day09::prepare(vec![vec![1, 2], vec![]]).is_err());
+}
+
+#[test]
+fn day10_example() {
+  let input = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...\n";
+  assert_eq!(solve::<Day10>(input), answers(8, 1));
+}
+
+#[test]
+fn day10_needs_a_start() {
+  assert!(matches!(Day10::parse("F7\nLJ\n"), Err(nom::Err::Failure(_))));
+}
+
+#[test]
+fn day14_example() {
+  let input = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+  assert_eq!(solve::<Day14>(input), answers(136, 64));
+}
+
+#[test]
+fn day20_counters_can_start_out_of_phase() {
+  // p sends a high pulse on every second press, starting with the third. q does
+  // so on every fourth press, also starting with the third. The lcm of the loop
+  // lengths would say they first line up on the fourth press.
+  let input = "\
+broadcaster -> a, c
+%a -> pi
+&pi -> p
+&p -> fin
+%c -> ci
+&ci -> d
+%d -> q
+&q -> fin
+&fin -> rx
+";
+  let (_, input) = Day20::parse(input).unwrap();
+  let input = Day20::prepare(input).unwrap();
+  assert_eq!(Day20::part_2(&input), 3);
+}
+
+#[test]
+fn day20_counts_high_pulses_on_the_first_press() {
+  let input = "broadcaster -> p, q
+&p -> fin
+&q -> fin
+&fin -> rx
+";
+  let (_, input) = Day20::parse(input).unwrap();
+  let input = Day20::prepare(input).unwrap();
+  assert_eq!(Day20::part_2(&input), 1);
+}
+
+#[test]
+fn day24_example() {
+  // Part 1 uses the test area of the real input, where none of these cross
+  let input = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+  assert_eq!(solve::<Day24>(input), answers(0, 47));
+}
@@ -0,0 +1,26 @@
+// Deterministic mode is a global switch, so this is in a file of its own: the
+// tests in a file share a process, and might run while it is on.
+use aoc_template::utils::{set_deterministic, HashSet};
+
+// Turns deterministic mode back off when the test ends, also when it fails
+struct Deterministic;
+
+impl Deterministic {
+  fn on() -> Self {
+    set_deterministic(true);
+    Deterministic
+  }
+}
+
+impl Drop for Deterministic {
+  fn drop(&mut self) {
+    set_deterministic(false);
+  }
+}
+
+#[test]
+fn deterministic_maps_iterate_in_the_same_order() {
+  let _deterministic = Deterministic::on();
+  let keys = |n: usize| (0..n).collect::<HashSet<_>>().into_iter().collect::<Vec<_>>();
+  assert_eq!(keys(100), keys(100));
+}
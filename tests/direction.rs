@@ -0,0 +1,24 @@
+use aoc_template::utils::{parse_direction, Direction};
+
+#[test]
+fn directions_turn_and_convert() {
+  for dir in Direction::cardinals() {
+    assert_eq!(dir.turn_left().turn_right(), dir);
+    assert_eq!(Direction::from_pos(dir.to_pos()), Some(dir));
+  }
+  assert_eq!(Direction::all().count(), 8);
+  assert!(Direction::W.is_horizontal());
+}
+
+#[test]
+fn directions_reverse_and_parse() {
+  for dir in Direction::all() {
+    assert_eq!(dir.reverse().reverse(), dir);
+    assert_ne!(dir.reverse(), dir);
+  }
+  assert_eq!(Direction::N.turn_right().turn_right(), Direction::S);
+  assert_eq!(parse_direction("^rest"), Ok(("rest", Direction::N)));
+  assert_eq!(Direction::try_from('L'), Ok(Direction::W));
+  assert_eq!(Direction::try_from('x'), Err('x'));
+  assert!(parse_direction("x").is_err());
+}
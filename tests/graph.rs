@@ -0,0 +1,91 @@
+use aoc_template::utils::Graph;
+
+#[test]
+fn graphs_find_components_and_orders() {
+  let mut g = Graph::undirected();
+  g.add_edge("a", "b", 1);
+  g.add_edge("b", "c", 1);
+  g.add_edge("x", "y", 1);
+  assert_eq!(g.components().len(), 2);
+
+  let mut d = Graph::directed();
+  d.add_edge(1, 2, 1);
+  d.add_edge(2, 1, 1);
+  d.add_edge(2, 3, 1);
+  assert_eq!(d.strongly_connected_components().len(), 2);
+  assert_eq!(d.topological_sort(), None);
+}
+
+#[test]
+fn topological_sorts_put_every_edge_forward() {
+  let mut g = Graph::directed();
+  g.add_edge("c", "d", 1);
+  g.add_edge("a", "b", 1);
+  g.add_edge("b", "c", 1);
+  g.add_edge("a", "c", 1);
+  let order = g.topological_sort().unwrap();
+  let position = |label| order.iter().position(|id| *g.label(*id) == label).unwrap();
+  assert!(position("a") < position("b") && position("b") < position("c") && position("c") < position("d"));
+  assert_eq!(g.in_degree(g.id(&"c").unwrap()), 2);
+}
+
+#[test]
+fn dot_output_draws_every_edge_once() {
+  let mut g = Graph::undirected();
+  g.add_edge("a", "b", 1);
+  g.add_edge("b", "say \"c\"", 4);
+  assert_eq!(
+    g.to_dot(),
+    "graph {\n  0 [label=\"a\"];\n  1 [label=\"b\"];\n  2 [label=\"say \\\"c\\\"\"];\n  0 -- 1;\n  1 -- 2 [label=\"4\"];\n}\n"
+  );
+
+  let mut d = Graph::directed();
+  d.add_edge(1, 2, 1);
+  d.add_edge(2, 1, 3);
+  assert_eq!(
+    d.to_dot(),
+    "digraph {\n  0 [label=\"1\"];\n  1 [label=\"2\"];\n  0 -> 1;\n  1 -> 0 [label=\"3\"];\n}\n"
+  );
+}
+
+#[test]
+fn contracting_paths_keeps_the_junctions() {
+  // a - x - y - b, and b - c and b - d directly. Only x and y are corridors.
+  let mut g = Graph::undirected();
+  g.add_edge("a", "x", 1);
+  g.add_edge("x", "y", 2);
+  g.add_edge("y", "b", 3);
+  g.add_edge("b", "c", 4);
+  g.add_edge("b", "d", 5);
+  let contracted = g.contract_paths();
+  assert_eq!(contracted.len(), 4);
+  assert_eq!(contracted.id(&"x"), None);
+  let (a, b, c) = (contracted.id(&"a").unwrap(), contracted.id(&"b").unwrap(), contracted.id(&"c").unwrap());
+  assert_eq!(contracted.neighbours(a).collect::<Vec<_>>(), vec![(b, 6)]);
+  assert_eq!(contracted.degree(b), 3);
+  assert_eq!(contracted.neighbours(c).collect::<Vec<_>>(), vec![(b, 4)]);
+}
+
+#[test]
+fn contracting_paths_drops_bare_cycles_and_keeps_loops() {
+  // A triangle on its own has no junctions, so nothing is left of it
+  let mut triangle = Graph::undirected();
+  triangle.add_edge(1, 2, 1);
+  triangle.add_edge(2, 3, 1);
+  triangle.add_edge(3, 1, 1);
+  assert!(triangle.contract_paths().is_empty());
+
+  // A loop through 1 and 2 that starts and ends at junction 0, which also has
+  // a dead end at 3
+  let mut lollipop = Graph::undirected();
+  lollipop.add_edge(0, 1, 1);
+  lollipop.add_edge(1, 2, 1);
+  lollipop.add_edge(2, 0, 1);
+  lollipop.add_edge(0, 3, 5);
+  let contracted = lollipop.contract_paths();
+  assert_eq!(contracted.len(), 2);
+  let (junction, end) = (contracted.id(&0).unwrap(), contracted.id(&3).unwrap());
+  let mut edges: Vec<_> = contracted.neighbours(junction).collect();
+  edges.sort();
+  assert_eq!(edges, vec![(junction, 3), (end, 5)]);
+}
@@ -0,0 +1,35 @@
+use aoc_template::utils::Grid;
+
+mod common;
+
+use common::{maze, pos};
+
+#[test]
+fn grids_map_and_iterate() {
+  let grid = maze();
+  assert_eq!(grid.iter().filter(|(_, open)| **open).count(), 11);
+  let inverted = grid.map(|open| !open);
+  assert!(inverted[pos(3, 0)]);
+  assert_eq!(grid.rows().count(), 4);
+  assert_eq!(grid.neighbours_8(pos(1, 1)).count(), 8);
+}
+
+#[test]
+fn grids_stay_inside_their_bounds() {
+  let grid = maze();
+  assert_eq!(grid.max_pos(), pos(3, 3));
+  assert_eq!(grid.get(pos(4, 0)), None);
+  assert_eq!(grid.get(pos(0, -1)), None);
+  // Corners only have the neighbours inside the grid
+  assert_eq!(grid.neighbours_4(pos(0, 0)).collect::<Vec<_>>(), vec![pos(1, 0), pos(0, 1)]);
+  assert_eq!(grid.neighbours_8(pos(3, 3)).count(), 3);
+}
+
+#[test]
+fn grids_find_and_update_values() {
+  let mut grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+  assert_eq!(grid.find(|v| *v > 4), Some(pos(1, 1)));
+  *grid.get_mut(pos(2, 0)).unwrap() = 9;
+  assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![9, 6]);
+  assert_eq!(grid.row(0), &[1, 2, 9]);
+}
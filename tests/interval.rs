@@ -0,0 +1,65 @@
+use aoc_template::utils::{Interval, IntervalBox, RangeSet};
+
+#[test]
+fn range_sets_map_pieces() {
+  let seeds: RangeSet<i64> = [Interval::new(0, 10)].into_iter().collect();
+  // Move 2..5 to 100..103 and leave the rest
+  let moved = seeds.map_pieces([(Interval::new(2, 5), 100)]);
+  assert_eq!(moved.len(), 10);
+  assert!(moved.contains(101) && !moved.contains(3));
+  assert_eq!(seeds.difference(&moved).len(), 3);
+}
+
+#[test]
+fn empty_intervals_contain_nothing() {
+  for empty in [Interval::new(5, 5), Interval::new(5, 3)] {
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.last(), None);
+    assert!(!empty.contains(5) && !empty.contains(4));
+    assert!(!empty.overlaps(&Interval::new(0, 10)));
+  }
+  // Splitting outside of an interval leaves one side empty
+  let (left, right) = Interval::new(2, 6).split_at(10);
+  assert_eq!((left, right.is_empty()), (Interval::new(2, 6), true));
+  let (left, right) = Interval::new(6, 2).split_at(4);
+  assert!(left.is_empty() && right.is_empty());
+  assert_eq!(IntervalBox([Interval::new(0, 3), Interval::new(1, 1)]).volume(), 0);
+}
+
+#[test]
+fn touching_intervals_do_not_overlap() {
+  let (a, b) = (Interval::new(0u32, 5), Interval::new(5, 8));
+  assert!(!a.overlaps(&b));
+  assert!(a.intersection(&b).is_empty());
+  assert_eq!(Interval::inclusive(0, 4), a);
+}
+
+#[test]
+fn range_sets_merge_touching_intervals() {
+  let mut set = RangeSet::new();
+  set.insert(Interval::new(5, 8));
+  set.insert(Interval::new(0, 5));
+  assert_eq!(set.intervals(), &[Interval::new(0, 8)]);
+  // Empty intervals are ignored, wherever they are
+  set.insert(Interval::new(20, 20));
+  set.insert(Interval::new(3, 1));
+  assert_eq!(set.intervals(), &[Interval::new(0, 8)]);
+  assert_eq!((set.min(), set.max()), (Some(0), Some(7)));
+}
+
+#[test]
+fn empty_range_sets_stay_empty() {
+  let empty: RangeSet<i64> = RangeSet::new();
+  let some: RangeSet<i64> = [Interval::new(0, 4)].into_iter().collect();
+  assert!(empty.is_empty());
+  assert_eq!((empty.len(), empty.min(), empty.max()), (0, None, None));
+  assert_eq!(empty.union(&some), some);
+  assert!(empty.intersection(&some).is_empty());
+  assert_eq!(some.difference(&empty), some);
+  assert!(some.difference(&some).is_empty());
+  // Removing what only touches the set removes nothing
+  let touching: RangeSet<i64> = [Interval::new(4, 9)].into_iter().collect();
+  assert_eq!(some.difference(&touching), some);
+  assert!(some.intersection(&touching).is_empty());
+}
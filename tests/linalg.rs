@@ -0,0 +1,35 @@
+use aoc_template::utils::{rational, Intersection, LinalgError, Line, Matrix, Point2, Point3};
+
+#[test]
+fn linear_systems_detect_singular_ones() {
+  let m = Matrix::from_rows(&[vec![1, 2], vec![2, 4]]);
+  assert_eq!(m.determinant(), Ok(rational(0)));
+  assert_eq!(m.solve(&[rational(1), rational(2)]), Err(LinalgError::Singular));
+  assert_eq!(m.solve(&[rational(1), rational(3)]), Err(LinalgError::Inconsistent));
+  assert_eq!(Matrix::identity(3).rank(), 3);
+}
+
+#[test]
+fn lines_can_be_parallel_or_skew() {
+  let a = Line::segment(Point2::new(0, 0), Point2::new(4, 4));
+  let b = Line::segment(Point2::new(0, 1), Point2::new(4, 5));
+  assert_eq!(a.intersect(&b), Intersection::Parallel);
+  let c = Line::segment(Point2::new(6, 6), Point2::new(8, 8));
+  assert_eq!(a.intersect(&c), Intersection::Overlapping);
+  assert_eq!(a.intersect_segment(&c), Intersection::Apart);
+
+  let x = Line::new(Point3::new(0, 0, 0), Point3::new(1, 0, 0));
+  let y = Line::new(Point3::new(0, 1, 1), Point3::new(0, 1, 0));
+  assert_eq!(x.intersect(&y), Intersection::Skew);
+}
+
+#[test]
+fn lines_need_a_direction() {
+  let point = Line::new(Point2::new(1, 1), Point2::new(0, 0));
+  let line = Line::segment(Point2::new(0, 0), Point2::new(2, 2));
+  assert!(!point.has_direction());
+  assert_eq!(point.param_of(&[rational(1), rational(1)]), None);
+  assert_eq!(line.param_of(&[rational(1), rational(1)]), Some(rational(1) / rational(2)));
+  assert_eq!(point.intersect(&line), Intersection::NoDirection);
+  assert_eq!(line.intersect_segment(&point), Intersection::NoDirection);
+}
@@ -0,0 +1,39 @@
+use aoc_template::utils::{crt, first_common_time, lcm_all, mod_inverse, CrtError};
+
+#[test]
+fn crt_handles_moduli_that_are_not_coprime() {
+  assert_eq!(crt([(1, 4), (3, 6)]), Ok((9, 12)));
+  assert_eq!(crt([(0, 4), (1, 6)]), Err(CrtError::NoSolution));
+  assert_eq!(first_common_time([(0, 3), (1, 4)]), Ok(9));
+}
+
+#[test]
+fn cycles_can_start_out_of_phase() {
+  assert_eq!(first_common_time([(2, 2), (2, 4)]), Ok(2));
+  assert_eq!(first_common_time([(3, 2), (3, 4)]), Ok(3));
+  // The events are only at or after the offsets
+  assert_eq!(first_common_time([(7, 3), (2, 5)]), Ok(7));
+  assert_eq!(first_common_time([(3, 2), (2, 4)]), Err(CrtError::NoSolution));
+}
+
+#[test]
+fn lcm_of_nothing_is_one() {
+  assert_eq!(lcm_all([4, 6, 10]), Some(60));
+  assert_eq!(lcm_all(Vec::<i64>::new()), Some(1));
+}
+
+#[test]
+fn lcm_that_overflows_is_none() {
+  assert_eq!(lcm_all([u8::MAX, 2]), None);
+  assert_eq!(lcm_all([u8::MAX, 0, 2]), Some(0));
+}
+
+#[test]
+fn moduli_have_to_be_positive() {
+  assert_eq!(crt([(1, 0)]), Err(CrtError::NonPositiveModulus(0)));
+  assert_eq!(crt([(1, 3), (1, -5)]), Err(CrtError::NonPositiveModulus(-5)));
+  assert_eq!(first_common_time([(2, 0)]), Err(CrtError::NonPositiveModulus(0)));
+  assert_eq!(mod_inverse(3, 0), None);
+  assert_eq!(mod_inverse(3, -7), None);
+  assert_eq!(mod_inverse(3, 7), Some(5));
+}
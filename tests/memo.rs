@@ -0,0 +1,16 @@
+use aoc_template::utils::Memo;
+
+#[test]
+fn memos_cache_across_calls() {
+  fn paths(x: usize, y: usize, memo: &mut Memo<(usize, usize), u64>) -> u64 {
+    if x == 0 || y == 0 {
+      return 1;
+    }
+    memo.get_or_compute((x, y), |memo| paths(x - 1, y, memo) + paths(x, y - 1, memo))
+  }
+  let mut memo = Memo::new();
+  assert_eq!(paths(16, 16, &mut memo), 601080390);
+  assert_eq!(memo.len(), 256);
+  memo.clear();
+  assert!(memo.is_empty());
+}
@@ -0,0 +1,17 @@
+use aoc_template::utils::{draw, read_drawing, read_letters, Grid};
+
+#[test]
+fn ocr_reads_what_it_draws() {
+  let rows = [".##..###.", "#..#.#..#", "#..#.###.", "####.#..#", "#..#.#..#", "#..#.###."];
+  let grid = Grid::from_rows(rows.iter().map(|r| r.chars().map(|c| c == '#').collect()).collect());
+  assert_eq!(read_letters(&grid), Some("AB".to_owned()));
+  assert_eq!(draw(&grid).lines().next(), Some(".##..###."));
+}
+
+#[test]
+fn ocr_rejects_unknown_letters_and_heights() {
+  // Only five pixels high
+  assert_eq!(read_drawing("#..#\n#..#\n####\n#..#\n#..#"), None);
+  // Six pixels high, but not a letter
+  assert_eq!(read_drawing("#...\n.#..\n..#.\n...#\n..#.\n.#.."), None);
+}
@@ -0,0 +1,82 @@
+// The plugins are built with flags for shared libraries that only unix
+// compilers understand
+#![cfg(unix)]
+
+mod common;
+
+use aoc_template::plugin::{Plugin, PluginError, AOC_PLUGIN_ABI_VERSION};
+use std::env::consts::DLL_EXTENSION;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Builds tests/c/plugin.c into its own directory, for the given ABI version
+fn build_plugin(name: &str, abi_version: u32) -> PathBuf {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+  fs::create_dir_all(&dir).unwrap();
+  let out = dir.join(format!("day06.{}", DLL_EXTENSION));
+  let define = format!("-DPLUGIN_ABI_VERSION={}", abi_version);
+  common::compile_c("plugin.c", &out, &["-shared", "-fPIC", &define]);
+  dir
+}
+
+// Runs day 6 with the binary, in a directory next to the plugins with the
+// example input
+fn run_day_6(plugin_dir: &PathBuf) -> (String, String) {
+  run_with_plugins(plugin_dir, "6")
+}
+
+// Runs a day, or all of them, with the binary, in a directory next to the
+// plugins with the example input of day 6
+fn run_with_plugins(plugin_dir: &PathBuf, days: &str) -> (String, String) {
+  let work_dir = plugin_dir.with_extension("work");
+  fs::create_dir_all(work_dir.join("inputs")).unwrap();
+  fs::write(work_dir.join("inputs/day06.txt"), "Time:      7  15   30\nDistance:  9  40  200\n").unwrap();
+  let output = Command::new(env!("CARGO_BIN_EXE_aoc-template"))
+    .current_dir(&work_dir)
+    .args(["run", days, "--plugins"])
+    .arg(plugin_dir)
+    .output()
+    .unwrap();
+  (
+    String::from_utf8_lossy(&output.stdout).into_owned(),
+    String::from_utf8_lossy(&output.stderr).into_owned(),
+  )
+}
+
+#[test]
+fn plugins_solve_their_day() {
+  let dir = build_plugin("plugins_current", AOC_PLUGIN_ABI_VERSION);
+  let plugin = Plugin::load(&dir.join(format!("day06.{}", DLL_EXTENSION))).unwrap();
+  assert_eq!(plugin.day(), 6);
+  assert_eq!(plugin.solve_part("a\nb\nc\n", 1).unwrap(), "3");
+  assert_eq!(plugin.solve_part("a\nb\nc\n", 2).unwrap(), "6");
+}
+
+#[test]
+fn plugins_replace_built_in_days() {
+  let dir = build_plugin("plugins_run", AOC_PLUGIN_ABI_VERSION);
+  let (stdout, _) = run_day_6(&dir);
+  assert!(stdout.contains("Part 1: 2\n"), "{}", stdout);
+  assert!(stdout.contains("Part 2: 44\n"), "{}", stdout);
+  assert!(stdout.contains("Part 2 took"), "{}", stdout);
+}
+
+#[test]
+fn plugins_for_another_abi_are_rejected() {
+  let dir = build_plugin("plugins_old", AOC_PLUGIN_ABI_VERSION + 1);
+  let res = Plugin::load(&dir.join(format!("day06.{}", DLL_EXTENSION)));
+  assert!(matches!(res, Err(PluginError::AbiVersion(v)) if v == AOC_PLUGIN_ABI_VERSION + 1));
+
+  // The built-in day runs instead
+  let (stdout, stderr) = run_day_6(&dir);
+  assert!(stdout.contains("Part 1: 288\n"), "{}", stdout);
+  assert!(stderr.contains("Skipping plugin"), "{}", stderr);
+}
+
+#[test]
+fn plugins_are_only_checked_once_for_all_days() {
+  let dir = build_plugin("plugins_all", AOC_PLUGIN_ABI_VERSION + 1);
+  let (_, stderr) = run_with_plugins(&dir, "--all");
+  assert_eq!(stderr.matches("Skipping plugin").count(), 1, "{}", stderr);
+}
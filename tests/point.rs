@@ -0,0 +1,19 @@
+use aoc_template::utils::{Point, Point3, Pos, Pos64};
+
+#[test]
+fn points_measure_distances() {
+  let a = Point3::new(1, -2, 3);
+  let b = Point3::new(-1, 2, 4);
+  assert_eq!(a.manhattan(b), 7);
+  assert_eq!(a.chebyshev(b), 4);
+  assert_eq!(Point::bounding_box([a, b]), Some((Point3::new(-1, -2, 3), Point3::new(1, 2, 4))));
+}
+
+#[test]
+fn positions_are_2d_points() {
+  let p = Pos::new(3, -4) + Pos::new(1, 1);
+  assert_eq!((p.x(), p.y()), (4, -3));
+  assert_eq!(p.to_string(), "4,-3");
+  assert_eq!(Pos64::from(p) * 1_000_000_000, Pos64::new(4_000_000_000, -3_000_000_000));
+  assert_eq!(<(i32, i32)>::from(p - Pos::new(4, 0)), (0, -3));
+}
@@ -0,0 +1,48 @@
+use aoc_template::utils::{boundary_points, covered_cells, interior_points, rational, signed_area, twice_signed_area, Pos64};
+use num::BigRational;
+
+mod common;
+
+use common::pos;
+
+#[test]
+fn polygons_count_their_cells() {
+  // The outline of an L of cells: 2 wide and 4 high, and 2 by 2 more at the
+  // bottom right
+  let corners = [pos(0, 0), pos(1, 0), pos(1, 2), pos(3, 2), pos(3, 3), pos(0, 3)];
+  assert_eq!(twice_signed_area(&corners), 10);
+  assert_eq!(boundary_points(&corners), 12);
+  assert_eq!(covered_cells(&corners), 12);
+}
+
+#[test]
+fn polygons_work_either_way_round_and_at_scale() {
+  let big: i128 = 1_000_000_000;
+  let side = big as i64;
+  let square = [Pos64::new(0, 0), Pos64::new(side, 0), Pos64::new(side, side), Pos64::new(0, side)];
+  let mut reversed = square;
+  reversed.reverse();
+  assert_eq!(twice_signed_area(&square), 2 * big * big);
+  assert_eq!(twice_signed_area(&reversed), -2 * big * big);
+  assert_eq!(covered_cells(&reversed), (big + 1) * (big + 1));
+  assert_eq!(interior_points(&square), (big - 1) * (big - 1));
+}
+
+#[test]
+fn polygons_have_exact_areas() {
+  let triangle = [pos(0, 0), pos(1, 0), pos(0, 1)];
+  assert_eq!(twice_signed_area(&triangle), 1);
+  assert_eq!(signed_area(&triangle), BigRational::new(1.into(), 2.into()));
+  let mut reversed = triangle;
+  reversed.reverse();
+  assert_eq!(signed_area(&reversed), BigRational::new((-1).into(), 2.into()));
+}
+
+#[test]
+fn polygons_too_large_for_an_i64_are_fine() {
+  let far = i64::MAX;
+  let square = [Pos64::new(0, 0), Pos64::new(far, 0), Pos64::new(far, far), Pos64::new(0, far)];
+  let side = far as i128;
+  assert_eq!(boundary_points(&square), 4 * side);
+  assert_eq!(signed_area(&square), rational(side) * rational(side));
+}
@@ -0,0 +1,62 @@
+use aoc_template::runner::{check_answers, on_big_stack, plugin_paths, Outcome, Task, Workers};
+use aoc_template::utils::HashMap;
+use aoc_template::MyErr;
+use std::path::{Path, PathBuf};
+
+fn answer(a: &str, b: &str) -> (String, String) {
+  (a.to_owned(), b.to_owned())
+}
+
+fn workers() -> Workers {
+  Workers {
+    exe: PathBuf::from(env!("CARGO_BIN_EXE_aoc-template")),
+    stack_size: 16,
+    deterministic: false,
+    log_args: vec![],
+    plugins: HashMap::default(),
+  }
+}
+
+#[test]
+fn threads_report_panics() {
+  assert!(on_big_stack("fine".to_owned(), 1, || {}));
+  assert!(!on_big_stack("panics".to_owned(), 1, || panic!("Oops")));
+}
+
+#[test]
+fn checks_count_every_distinct_answer() {
+  let mut run = 0;
+  let answers = check_answers(5, || {
+    run += 1;
+    Ok(if run % 2 == 0 { answer("1", "2") } else { answer("1", "3") })
+  });
+  assert_eq!(answers.unwrap(), vec![(answer("1", "3"), 3), (answer("1", "2"), 2)]);
+}
+
+#[test]
+fn checks_stop_at_the_first_error() {
+  let mut run = 0;
+  let answers = check_answers(5, || {
+    run += 1;
+    if run == 2 {
+      Err(MyErr::Other("No answer".to_owned()))
+    } else {
+      Ok(answer("1", "2"))
+    }
+  });
+  assert!(answers.is_err());
+  assert_eq!(run, 2);
+}
+
+#[test]
+fn directories_without_libraries_have_no_plugins() {
+  assert!(plugin_paths(None).is_empty());
+  assert!(plugin_paths(Some(Path::new("inputs"))).is_empty());
+}
+
+#[test]
+fn workers_run_and_check_days() {
+  let workers = workers();
+  assert_eq!(workers.run_day(1, &Task::Run), Outcome::Succeeded);
+  assert_eq!(workers.run_day(1, &Task::Check(2)), Outcome::Succeeded);
+}
@@ -0,0 +1,83 @@
+use aoc_template::script::{Script, ScriptError};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const MAZE: &str = "S..#\n.#.#\n.#..\n...E";
+
+#[test]
+fn scripts_use_grids_and_searches() {
+  let script = Script::new(
+    r#"
+      fn part1(input) {
+        let grid = grid(input);
+        let res = bfs(grid.find('S'), |p| neighbours_4(grid, p).filter(|n| grid[n] != '#'));
+        res.path_to(grid.find('E')).len() - 1
+      }
+      // Steps down cost 10, and every path to E takes three of them
+      fn part2(input) {
+        let grid = grid(input);
+        let next = |p| neighbours_4(grid, p).filter(|n| grid[n] != '#').map(|n| [n, if n.y > p.y { 10 } else { 1 }]);
+        let res = dijkstra(grid.find('S'), next, |p| grid[p] == 'E');
+        `${res.goal} ${res.goal_cost}`
+      }
+    "#,
+  )
+  .unwrap();
+  assert_eq!(script.solve_part(MAZE, 1).unwrap(), "6");
+  assert_eq!(script.solve_part(MAZE, 2).unwrap(), "3,3 33");
+}
+
+#[test]
+fn script_errors_are_returned() {
+  let script = Script::new("fn part1(input) { bfs(pos(0, 0), |p| [p.x]) }").unwrap();
+  assert!(matches!(script.solve_part("", 1), Err(ScriptError::Eval(_))));
+  // part2 isn't defined
+  assert!(matches!(script.solve_part("", 2), Err(ScriptError::Eval(_))));
+  assert!(matches!(Script::new("fn part1("), Err(ScriptError::Compile(_))));
+}
+
+#[test]
+fn scripts_run_through_the_binary_and_are_verified() {
+  let work_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("scripts_run");
+  fs::create_dir_all(work_dir.join("inputs")).unwrap();
+  fs::write(work_dir.join("inputs/day06.txt"), "Time:      7  15   30\nDistance:  9  40  200\n").unwrap();
+  let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("scripts/day06.rhai");
+  let output = Command::new(env!("CARGO_BIN_EXE_aoc-template"))
+    .current_dir(&work_dir)
+    .args(["run", "6", "--script"])
+    .arg(script)
+    .output()
+    .unwrap();
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Part 1: 288\n"), "{}", stdout);
+  assert!(stdout.contains("Part 2 took"), "{}", stdout);
+  assert!(stdout.contains("Part 1 matches the built-in solution"), "{}", stdout);
+  assert!(stdout.contains("Part 2 matches the built-in solution"), "{}", stdout);
+}
+
+#[test]
+fn scripts_get_errors_instead_of_wrapped_numbers() {
+  let script = Script::new(
+    r#"
+      fn part1(input) { pos(3000000000, 0) }
+      fn part2(input) { dijkstra(pos(0, 0), |p| [[pos(1, 0), -1]]) }
+    "#,
+  )
+  .unwrap();
+  assert!(matches!(script.solve_part("", 1), Err(ScriptError::Eval(_))));
+  assert!(matches!(script.solve_part("", 2), Err(ScriptError::Eval(_))));
+  let setter = Script::new("fn part1(input) { let p = pos(0, 0); p.y = -3000000000; p }").unwrap();
+  assert!(matches!(setter.solve_part("", 1), Err(ScriptError::Eval(_))));
+}
+
+#[test]
+fn scripts_cannot_be_combined_with_plugins() {
+  let output = Command::new(env!("CARGO_BIN_EXE_aoc-template"))
+    .args(["run", "6", "--script", "day06.rhai", "--plugins", "plugins"])
+    .output()
+    .unwrap();
+  assert!(!output.status.success());
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("cannot be used with"), "{}", stderr);
+}
@@ -0,0 +1,54 @@
+use aoc_template::utils::{astar, bfs, multi_bfs, BucketQueue, Pos, Search};
+
+mod common;
+
+use common::{maze, pos};
+
+#[test]
+fn bfs_finds_shortest_paths_in_a_maze() {
+  let grid = maze();
+  let open = |p: &Pos| grid.neighbours_4(*p).filter(|n| grid[*n]).collect::<Vec<_>>();
+  let res = Search::with_paths().bfs(pos(0, 0), open, |p| *p == pos(3, 2));
+  assert_eq!(res.goal_cost(), Some(5));
+  let path = res.goal_path().unwrap();
+  assert_eq!(path.len(), 6);
+  assert!(path.windows(2).all(|w| w[0].x().abs_diff(w[1].x()) + w[0].y().abs_diff(w[1].y()) == 1));
+  // Without tracking there are no paths, but the costs are the same
+  let plain = bfs(pos(0, 0), open, |_| false);
+  assert_eq!(plain.path_to(&pos(3, 2)), None);
+  assert_eq!(plain.cost(&pos(3, 2)), Some(5));
+}
+
+#[test]
+fn multi_source_bfs_measures_from_the_nearest_start() {
+  let grid = maze();
+  let open = |p: &Pos| grid.neighbours_4(*p).filter(|n| grid[*n]).collect::<Vec<_>>();
+  let res = multi_bfs([pos(0, 0), pos(3, 2)], open, |_| false);
+  assert_eq!(res.cost(&pos(2, 0)), Some(2));
+  assert_eq!(res.cost(&pos(2, 2)), Some(1));
+  assert_eq!(res.stats.expanded, 11);
+}
+
+#[test]
+fn astar_agrees_with_dijkstra() {
+  // Moving right costs 1 and moving up costs 3, to get from 0,0 to 5,5
+  let successors = |p: &Pos| [(pos(p.x() + 1, p.y()), 1), (pos(p.x(), p.y() + 1), 3)].into_iter().filter(|(n, _)| n.x() <= 5 && n.y() <= 5);
+  let goal = |p: &Pos| *p == pos(5, 5);
+  let with_heuristic = astar(pos(0, 0), successors, |p| (5 - p.x() + 3 * (5 - p.y())) as usize, goal);
+  let without = Search::default().dijkstra(pos(0, 0), successors, goal);
+  assert_eq!(with_heuristic.goal_cost(), Some(20));
+  assert_eq!(without.goal_cost(), Some(20));
+  assert!(with_heuristic.stats.expanded <= without.stats.expanded);
+}
+
+#[test]
+fn bucket_queues_pop_the_lowest_priority() {
+  let mut q = BucketQueue::new();
+  q.push(5, 'a');
+  q.push(2, 'b');
+  q.push(7, 'c');
+  assert_eq!(q.pop(), Some((2, 'b')));
+  q.push(1, 'd');
+  assert_eq!(q.pop(), Some((1, 'd')));
+  assert_eq!(q.len(), 2);
+}
@@ -0,0 +1,29 @@
+use aoc_template::utils::{Polynomial, Sequence, SequenceError};
+
+#[test]
+fn sequences_extrapolate_both_ways() {
+  let seq = Sequence::from_samples(&[10, 13, 16, 21, 30, 45]).unwrap();
+  assert_eq!(seq.at(6), 68.into());
+  assert_eq!(seq.at(-1), 5.into());
+  assert_eq!(Sequence::from_samples::<i32>(&[]), Err(SequenceError::NoSamples));
+  assert_eq!(Polynomial::fit(&[(1, 2), (1, 3)]), Err(SequenceError::DuplicateX));
+}
+
+#[test]
+fn a_single_sample_is_constant() {
+  let seq = Sequence::from_samples(&[5]).unwrap();
+  assert_eq!(seq.degree(), 0);
+  assert_eq!(seq.at(3), 5.into());
+  assert_eq!(seq.at(-1), 5.into());
+}
+
+#[test]
+fn a_single_difference_is_constant() {
+  let seq = Sequence::from_samples(&[1, 2, 4]).unwrap();
+  assert_eq!(seq.degree(), 2);
+  assert_eq!(seq.at(3), 7.into());
+  assert_eq!(seq.at(-1), 1.into());
+  let line = Sequence::from_samples(&[3, 5]).unwrap();
+  assert_eq!(line.degree(), 1);
+  assert_eq!(line.at(2), 7.into());
+}
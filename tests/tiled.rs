@@ -0,0 +1,24 @@
+mod common;
+
+use common::{maze, pos};
+
+#[test]
+fn tiled_bfs_crosses_tiles() {
+  let grid = maze();
+  let reached = grid.tiled().bfs(pos(0, 0), 6, |open| *open);
+  assert!(reached.keys().any(|p| p.x() < 0 || p.y() < 0));
+  assert!(reached.values().all(|d| *d <= 6));
+}
+
+#[test]
+fn tiled_bfs_stops_at_max_steps() {
+  let grid = maze();
+  let tiled = grid.tiled();
+  for max_steps in 0..8 {
+    let reached = tiled.bfs(pos(0, 0), max_steps, |open| *open);
+    assert_eq!(reached.values().max(), Some(&max_steps));
+  }
+  // Going left from the start wraps around to the wall on the right
+  assert!(!tiled[pos(-1, 0)]);
+  assert_eq!(tiled.local(pos(-1, -5)), pos(3, 3));
+}